@@ -0,0 +1,69 @@
+#![no_main]
+use bacnet_parse::nsdu::apdu::confirmed_request_pdu::ConfirmedRequestHeader;
+use bacnet_parse::nsdu::apdu::cov::{
+    COVNotification, SubscribeCOVPropertyRequest, SubscribeCOVRequest,
+};
+use bacnet_parse::nsdu::apdu::read_property::{ReadPropertyACK, ReadPropertyRequest};
+use bacnet_parse::nsdu::apdu::read_property_multiple::{
+    ReadAccessResults, ReadAccessSpecifications,
+};
+use bacnet_parse::nsdu::apdu::tag::TagWalker;
+use bacnet_parse::nsdu::apdu::unconfirmed_request_pdu::UnconfirmedServiceChoice;
+use bacnet_parse::nsdu::apdu::write_property::{WriteAccessSpecifications, WritePropertyRequest};
+use bacnet_parse::nsdu::apdu::*;
+use libfuzzer_sys::fuzz_target;
+
+/// Run every service decoder over the bytes, regardless of the service choice.
+fn parse_service(bytes: &[u8]) {
+    let _ = ReadPropertyRequest::parse(bytes);
+    if let Ok(ack) = ReadPropertyACK::parse(bytes) {
+        for _ in ack.values() {}
+    }
+    for spec in ReadAccessSpecifications::from(bytes).flatten() {
+        for _ in spec.property_references() {}
+    }
+    for result in ReadAccessResults::from(bytes).flatten() {
+        for read in result.results().flatten() {
+            if let Some(values) = read.result.values() {
+                for _ in values {}
+            }
+        }
+    }
+    if let Ok(wp) = WritePropertyRequest::parse(bytes) {
+        for _ in wp.values() {}
+    }
+    for spec in WriteAccessSpecifications::from(bytes).flatten() {
+        for value in spec.properties().flatten() {
+            for _ in value.values() {}
+        }
+    }
+    let _ = SubscribeCOVRequest::parse(bytes);
+    let _ = SubscribeCOVPropertyRequest::parse(bytes);
+    if let Ok(cov) = COVNotification::parse(bytes) {
+        for _ in cov.values() {}
+    }
+    for _ in TagWalker::from(bytes) {}
+}
+
+fuzz_target!(|data: &[u8]| {
+    let apdu = match bacnet_parse::parse_apdu(data) {
+        Ok(apdu) => apdu,
+        Err(_) => return,
+    };
+    let _ = apdu.pdu_type();
+    let _ = apdu.confirmed_service_choice();
+    if let Ok(header) = ConfirmedRequestHeader::parse(&apdu) {
+        let _ = header.max_segments_accepted();
+        let _ = header.max_apdu_length_accepted();
+        parse_service(header.service_request());
+    }
+    let _ = UnconfirmedServiceChoice::parse(&apdu);
+    let _ = SimpleACKPDU::parse(&apdu);
+    if let Ok(ack) = ComplexACKPDU::parse(&apdu) {
+        parse_service(ack.service_ack());
+    }
+    let _ = SegmentACKPDU::parse(&apdu);
+    let _ = RejectPDU::parse(&apdu);
+    let _ = AbortPDU::parse(&apdu);
+    let _ = ErrorPDU::parse(&apdu);
+});
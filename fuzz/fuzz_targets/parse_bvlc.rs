@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bvlc) = bacnet_parse::parse_bvlc(data) {
+        let _ = bvlc.bvlc_function();
+        let _ = bvlc.ip_port();
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bvlc) = bacnet_parse::parse_bvlc6(data) {
+        let _ = bvlc.result_code();
+        let _ = bvlc.ttl();
+    }
+});
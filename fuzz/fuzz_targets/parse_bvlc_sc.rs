@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(bvlc) = bacnet_parse::parse_bvlc_sc(data) {
+        bvlc.destination_options().for_each(drop);
+        bvlc.data_options().for_each(drop);
+        let _ = bvlc.payload();
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(frame) = bacnet_parse::parse_mstp(data) {
+        let _ = frame.frame_type();
+        let _ = frame.crcs();
+    }
+    let _ = bacnet_parse::parse_mstp_skip_crc_compute(data);
+});
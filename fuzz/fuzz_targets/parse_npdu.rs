@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(npdu) = bacnet_parse::npdu::parse_npdu(data) {
+        let _ = npdu.prio();
+        let _ = npdu.src();
+        let _ = npdu.dst_hopcount();
+        let _ = npdu.payload();
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut buf = [0u8; 1024];
+    let _ = bacnet_parse::parse_ptp(data);
+    let _ = bacnet_parse::parse_ptp_escaped(data, &mut buf);
+});
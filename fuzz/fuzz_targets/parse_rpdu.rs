@@ -0,0 +1,9 @@
+#![no_main]
+use bacnet_parse::nsdu::rpdu::RPDU;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(RPDU::IAmRouterToNetwork(dnets)) = bacnet_parse::parse_rpdu(data) {
+        for _ in dnets {}
+    }
+});
@@ -0,0 +1,98 @@
+//! BACnet over ARCNET, as described in Clause 8.
+use super::ethernet::LLCControl;
+use super::npdu::*;
+use super::*;
+
+/// Parses an ARCNET frame starting at the source MAC address, i.e. source, destination, system
+/// code, DSAP, SSAP and LLC control followed by the data.
+pub fn parse_arcnet(bytes: &[u8]) -> Result<ARCNETFrame<'_>, Error> {
+    if bytes.len() < 6 {
+        return Err(Error::Length(
+            "data is shorter than minimum arcnet frame size",
+        ));
+    }
+    if bytes[2] != 0xCD {
+        return Err(Error::InvalidValue("system code is not bacnet"));
+    }
+    if bytes[3] != 0x82 || bytes[4] != 0x82 {
+        return Err(Error::InvalidValue("llc sap is not bacnet"));
+    }
+    let mut frame = ARCNETFrame {
+        src_mac: bytes[0],
+        dst_mac: bytes[1],
+        llc_control: bytes[5].into(),
+        npdu: None,
+    };
+    if frame.llc_control() != LLCControl::UI {
+        return Ok(frame);
+    }
+    if let Ok(npdu) = parse_npdu(&bytes[6..]) {
+        frame.npdu = Some(npdu);
+    }
+    Ok(frame)
+}
+
+pub struct ARCNETFrame<'a> {
+    src_mac: u8,
+    dst_mac: u8,
+    llc_control: LLCControl,
+    npdu: Option<NPDU<'a>>,
+}
+
+impl<'a> ARCNETFrame<'a> {
+    pub fn src_mac(&self) -> u8 {
+        self.src_mac
+    }
+    pub fn dst_mac(&self) -> u8 {
+        self.dst_mac
+    }
+    pub fn llc_control(&self) -> LLCControl {
+        self.llc_control
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_arcnet_test() {
+        const DATA: &[u8] = &[
+            0x05, 0x0c, 0xcd, 0x82, 0x82, 0x03, // header
+            0x01, 0x0c, 0x00, 0x01, 0x06, 0xc0, 0xa8, 0x01, 0x12, 0xba, 0xc0, // NPDU
+            0x10, 0x08, // APDU
+        ];
+        let frame = parse_arcnet(DATA).unwrap();
+        assert_eq!(frame.src_mac(), 5);
+        assert_eq!(frame.dst_mac(), 12);
+        assert_eq!(frame.llc_control(), LLCControl::UI);
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert!(npdu.is_expecting_reply());
+        let src = npdu.src().as_ref().unwrap();
+        assert_eq!(src.net(), 1);
+        assert_eq!(src.addr().len(), 6);
+        assert_eq!(npdu.payload(), &[0x10, 0x08]);
+    }
+
+    #[test]
+    fn parse_arcnet_test_frame() {
+        const DATA: &[u8] = &[0x05, 0xff, 0xcd, 0x82, 0x82, 0xf3, 0xaa];
+        let frame = parse_arcnet(DATA).unwrap();
+        assert_eq!(frame.llc_control(), LLCControl::Test);
+        assert!(frame.npdu().is_none());
+    }
+
+    #[test]
+    fn parse_arcnet_invalid() {
+        assert!(parse_arcnet(&[0x05, 0x0c, 0xcd, 0x82, 0x82]).is_err());
+        assert!(parse_arcnet(&[0x05, 0x0c, 0xf0, 0x82, 0x82, 0x03]).is_err());
+        assert!(parse_arcnet(&[0x05, 0x0c, 0xcd, 0xaa, 0x82, 0x03]).is_err());
+        assert!(parse_arcnet(&[0x05, 0x0c, 0xcd, 0x82, 0xaa, 0x03]).is_err());
+        // no npdu, but still a valid frame
+        let frame = parse_arcnet(&[0x05, 0x0c, 0xcd, 0x82, 0x82, 0x03]).unwrap();
+        assert!(frame.npdu().is_none());
+    }
+}
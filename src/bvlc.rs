@@ -2,7 +2,9 @@ use super::npdu::*;
 use super::Error;
 use arrayref::array_ref;
 
-pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
+// `usize::is_multiple_of` is too recent for the toolchains this crate supports.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC<'_>, Error> {
     if slice.len() < 4 {
         return Err(Error::Length("insufficient size for bvlc"));
     }
@@ -15,11 +17,19 @@ pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
     if len > slice.len() {
         return Err(Error::Length("bvlc length too largu"));
     }
+    if len < 4 {
+        return Err(Error::Length("bvlc length too small"));
+    }
+    let slice = &slice[..len];
 
-    let mut bvlc = BVLC::default();
-    bvlc.bfn = slice[1].into();
-    let npdu_start_idx: usize = if bvlc.has_ip_port() {
-        if slice.len() < 6 {
+    let mut bvlc = BVLC {
+        bfn: slice[1].into(),
+        ip_port: None,
+        npdu: None,
+        payload: &[],
+    };
+    let payload_start_idx: usize = if bvlc.has_ip_port() {
+        if slice.len() < 10 {
             return Err(Error::Length("insufficient size for bvlc ip/port"));
         }
         bvlc.ip_port = Some(array_ref!(slice, 4, 6).into());
@@ -27,11 +37,24 @@ pub fn parse_bvlc(slice: &[u8]) -> Result<BVLC, Error> {
     } else {
         4
     };
-    if bvlc.has_npdu() {
-        if slice.len() < npdu_start_idx || len < npdu_start_idx {
-            return Err(Error::Length("insufficient size for npdu"));
+    bvlc.payload = &slice[payload_start_idx..];
+    match bvlc.bfn {
+        BVLCFunction::BVLCResult if bvlc.payload.len() < 2 => {
+            return Err(Error::Length("insufficient size for bvlc result"));
+        }
+        BVLCFunction::RegisterForeignDevice if bvlc.payload.len() < 2 => {
+            return Err(Error::Length("insufficient size for foreign device ttl"));
         }
-        if let Ok(npdu) = parse_npdu(&slice[npdu_start_idx..len]) {
+        BVLCFunction::WBDT | BVLCFunction::RBDTAck if bvlc.payload.len() % 10 != 0 => {
+            return Err(Error::Length("bdt entries are not a multiple of 10 bytes"));
+        }
+        BVLCFunction::RFDTAck if bvlc.payload.len() % 10 != 0 => {
+            return Err(Error::Length("fdt entries are not a multiple of 10 bytes"));
+        }
+        _ => (),
+    }
+    if bvlc.has_npdu() {
+        if let Ok(npdu) = parse_npdu(bvlc.payload) {
             bvlc.npdu = Some(npdu);
         }
     }
@@ -43,6 +66,7 @@ pub struct BVLC<'a> {
     bfn: BVLCFunction,
     ip_port: Option<IpPort>,
     npdu: Option<NPDU<'a>>,
+    payload: &'a [u8],
 }
 
 impl<'a> BVLC<'a> {
@@ -56,19 +80,147 @@ impl<'a> BVLC<'a> {
         &self.npdu
     }
     pub fn has_npdu(&self) -> bool {
-        match &self.bfn {
+        matches!(
+            &self.bfn,
             BVLCFunction::ForwardedNPDU
-            | BVLCFunction::UnicastNPDU
-            | BVLCFunction::BroadcastNPDU => true,
-            _ => false,
-        }
+                | BVLCFunction::DistributeBroadcastToNetwork
+                | BVLCFunction::UnicastNPDU
+                | BVLCFunction::BroadcastNPDU
+        )
     }
+    /// `true` for a Forwarded-NPDU, where `ip_port` is the original source, and for a
+    /// Delete-FDT-Entry, where `ip_port` is the entry to delete.
     pub fn has_ip_port(&self) -> bool {
-        match &self.bfn {
-            BVLCFunction::ForwardedNPDU => true,
-            _ => false,
+        has_ip_port(self.bfn)
+    }
+    /// The result code of a BVLC-Result.
+    pub fn result_code(&self) -> Option<BVLCResultCode> {
+        match self.bfn {
+            BVLCFunction::BVLCResult => self
+                .payload
+                .get(..2)
+                .map(|b| u16::from_be_bytes(*array_ref!(b, 0, 2)).into()),
+            _ => None,
+        }
+    }
+    /// The broadcast distribution table entries of a Write-BDT or Read-BDT-Ack.
+    pub fn bdt_entries(&self) -> Option<BDTEntries<'a>> {
+        match self.bfn {
+            BVLCFunction::WBDT | BVLCFunction::RBDTAck => Some(BDTEntries {
+                bytes: self.payload,
+            }),
+            _ => None,
+        }
+    }
+    /// The foreign device table entries of a Read-FDT-Ack.
+    pub fn fdt_entries(&self) -> Option<FDTEntries<'a>> {
+        match self.bfn {
+            BVLCFunction::RFDTAck => Some(FDTEntries {
+                bytes: self.payload,
+            }),
+            _ => None,
         }
     }
+    /// The time-to-live in seconds of a Register-Foreign-Device.
+    pub fn ttl(&self) -> Option<u16> {
+        match self.bfn {
+            BVLCFunction::RegisterForeignDevice => self
+                .payload
+                .get(..2)
+                .map(|b| u16::from_be_bytes(*array_ref!(b, 0, 2))),
+            _ => None,
+        }
+    }
+}
+
+fn has_ip_port(bfn: BVLCFunction) -> bool {
+    matches!(
+        bfn,
+        BVLCFunction::ForwardedNPDU | BVLCFunction::DeleteFDTEntry
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLCResultCode {
+    SuccessfulCompletion,
+    WriteBDTNAK,
+    ReadBDTNAK,
+    RegisterForeignDeviceNAK,
+    ReadFDTNAK,
+    DeleteFDTEntryNAK,
+    DistributeBroadcastToNetworkNAK,
+    Unknown(u16),
+}
+
+impl From<u16> for BVLCResultCode {
+    fn from(b: u16) -> Self {
+        match b {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0010 => Self::WriteBDTNAK,
+            0x0020 => Self::ReadBDTNAK,
+            0x0030 => Self::RegisterForeignDeviceNAK,
+            0x0040 => Self::ReadFDTNAK,
+            0x0050 => Self::DeleteFDTEntryNAK,
+            0x0060 => Self::DistributeBroadcastToNetworkNAK,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+/// An entry of a broadcast distribution table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BDTEntry {
+    pub ip_port: IpPort,
+    pub broadcast_mask: u32,
+}
+
+pub struct BDTEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl Iterator for BDTEntries<'_> {
+    type Item = BDTEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.len() < 10 {
+            return None;
+        }
+        let entry = BDTEntry {
+            ip_port: array_ref!(self.bytes, 0, 6).into(),
+            broadcast_mask: u32::from_be_bytes(*array_ref!(self.bytes, 6, 4)),
+        };
+        self.bytes = &self.bytes[10..];
+        Some(entry)
+    }
+}
+
+/// An entry of a foreign device table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FDTEntry {
+    pub ip_port: IpPort,
+    /// The time-to-live in seconds given when the foreign device registered.
+    pub ttl: u16,
+    /// The number of seconds remaining before the entry is purged.
+    pub remaining: u16,
+}
+
+pub struct FDTEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl Iterator for FDTEntries<'_> {
+    type Item = FDTEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.len() < 10 {
+            return None;
+        }
+        let entry = FDTEntry {
+            ip_port: array_ref!(self.bytes, 0, 6).into(),
+            ttl: u16::from_be_bytes(*array_ref!(self.bytes, 6, 2)),
+            remaining: u16::from_be_bytes(*array_ref!(self.bytes, 8, 2)),
+        };
+        self.bytes = &self.bytes[10..];
+        Some(entry)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -79,16 +231,14 @@ pub enum BVLCFunction {
     RBDTAck,
     ForwardedNPDU,
     RegisterForeignDevice,
+    RFDT,
+    RFDTAck,
+    DeleteFDTEntry,
+    DistributeBroadcastToNetwork,
     UnicastNPDU,
     BroadcastNPDU,
     SecureBVLL,
-    Unknown,
-}
-
-impl Default for BVLCFunction {
-    fn default() -> BVLCFunction {
-        Self::Unknown
-    }
+    Unknown(u8),
 }
 
 impl From<u8> for BVLCFunction {
@@ -100,14 +250,46 @@ impl From<u8> for BVLCFunction {
             0x03 => Self::RBDTAck,
             0x04 => Self::ForwardedNPDU,
             0x05 => Self::RegisterForeignDevice,
+            0x06 => Self::RFDT,
+            0x07 => Self::RFDTAck,
+            0x08 => Self::DeleteFDTEntry,
+            0x09 => Self::DistributeBroadcastToNetwork,
             0x0a => Self::UnicastNPDU,
             0x0b => Self::BroadcastNPDU,
             0x0c => Self::SecureBVLL,
-            _ => Self::Unknown,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+impl Default for BVLCFunction {
+    fn default() -> BVLCFunction {
+        Self::Unknown(0xff)
+    }
+}
+
+impl From<BVLCFunction> for u8 {
+    fn from(bfn: BVLCFunction) -> Self {
+        match bfn {
+            BVLCFunction::BVLCResult => 0x00,
+            BVLCFunction::WBDT => 0x01,
+            BVLCFunction::RBDT => 0x02,
+            BVLCFunction::RBDTAck => 0x03,
+            BVLCFunction::ForwardedNPDU => 0x04,
+            BVLCFunction::RegisterForeignDevice => 0x05,
+            BVLCFunction::RFDT => 0x06,
+            BVLCFunction::RFDTAck => 0x07,
+            BVLCFunction::DeleteFDTEntry => 0x08,
+            BVLCFunction::DistributeBroadcastToNetwork => 0x09,
+            BVLCFunction::UnicastNPDU => 0x0a,
+            BVLCFunction::BroadcastNPDU => 0x0b,
+            BVLCFunction::SecureBVLL => 0x0c,
+            BVLCFunction::Unknown(b) => b,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct IpPort {
     pub ip: u32,
     pub port: u16,
@@ -121,3 +303,293 @@ impl From<&[u8; 6]> for IpPort {
         }
     }
 }
+
+/// Write a BVLC header followed by the payload into `buf`. `ip_port` must be given for, and only
+/// for, a Forwarded-NPDU or Delete-FDT-Entry. Returns the number of bytes written.
+pub fn encode_bvlc(
+    buf: &mut [u8],
+    bfn: BVLCFunction,
+    ip_port: Option<&IpPort>,
+    payload: &[u8],
+) -> Result<usize, Error> {
+    if ip_port.is_some() != has_ip_port(bfn) {
+        return Err(Error::InvalidValue(
+            "ip/port must be given only for forwarded npdu or delete fdt entry",
+        ));
+    }
+    let header_len = if ip_port.is_some() { 10 } else { 4 };
+    let len = header_len + payload.len();
+    if len > usize::from(u16::MAX) {
+        return Err(Error::Length("bvlc length too large"));
+    }
+    if buf.len() < len {
+        return Err(Error::Length("insufficient buffer size for bvlc"));
+    }
+    buf[0] = 0x81;
+    buf[1] = bfn.into();
+    buf[2..4].copy_from_slice(&(len as u16).to_be_bytes());
+    if let Some(ip_port) = ip_port {
+        buf[4..8].copy_from_slice(&ip_port.ip.to_be_bytes());
+        buf[8..10].copy_from_slice(&ip_port.port.to_be_bytes());
+    }
+    buf[header_len..len].copy_from_slice(payload);
+    Ok(len)
+}
+
+/// Write an Original-Unicast-NPDU into `buf`. Returns the number of bytes written.
+pub fn encode_unicast_npdu(buf: &mut [u8], npdu: &[u8]) -> Result<usize, Error> {
+    encode_bvlc(buf, BVLCFunction::UnicastNPDU, None, npdu)
+}
+
+/// Write an Original-Broadcast-NPDU into `buf`. Returns the number of bytes written.
+pub fn encode_broadcast_npdu(buf: &mut [u8], npdu: &[u8]) -> Result<usize, Error> {
+    encode_bvlc(buf, BVLCFunction::BroadcastNPDU, None, npdu)
+}
+
+/// Write a Forwarded-NPDU from the original source `ip_port` into `buf`. Returns the number of
+/// bytes written.
+pub fn encode_forwarded_npdu(
+    buf: &mut [u8],
+    ip_port: &IpPort,
+    npdu: &[u8],
+) -> Result<usize, Error> {
+    encode_bvlc(buf, BVLCFunction::ForwardedNPDU, Some(ip_port), npdu)
+}
+
+/// Write a Register-Foreign-Device with the time-to-live in seconds into `buf`. Returns the
+/// number of bytes written.
+pub fn encode_register_foreign_device(buf: &mut [u8], ttl: u16) -> Result<usize, Error> {
+    encode_bvlc(
+        buf,
+        BVLCFunction::RegisterForeignDevice,
+        None,
+        &ttl.to_be_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NPDU: &[u8] = &[0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x08];
+
+    #[test]
+    fn encode_unicast_broadcast_test() {
+        let mut buf = [0u8; 64];
+        let len = encode_unicast_npdu(&mut buf, NPDU).unwrap();
+        assert_eq!(len, 12);
+        assert_eq!(&buf[..4], &[0x81, 0x0a, 0x00, 0x0c]);
+        let bvlc = parse_bvlc(&buf[..len]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::UnicastNPDU);
+        assert!(bvlc.ip_port().is_none());
+        assert_eq!(bvlc.npdu().as_ref().unwrap().payload(), &[0x10, 0x08]);
+
+        let len = encode_broadcast_npdu(&mut buf, NPDU).unwrap();
+        assert_eq!(&buf[..4], &[0x81, 0x0b, 0x00, 0x0c]);
+        let bvlc = parse_bvlc(&buf[..len]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::BroadcastNPDU);
+        assert!(bvlc.npdu().is_some());
+    }
+
+    #[test]
+    fn encode_forwarded_npdu_test() {
+        let mut buf = [0u8; 64];
+        let ip_port = IpPort {
+            ip: 0xc0a8_0101,
+            port: 0xbac0,
+        };
+        let len = encode_forwarded_npdu(&mut buf, &ip_port, NPDU).unwrap();
+        assert_eq!(len, 18);
+        assert_eq!(
+            &buf[..10],
+            &[0x81, 0x04, 0x00, 0x12, 0xc0, 0xa8, 0x01, 0x01, 0xba, 0xc0]
+        );
+        let bvlc = parse_bvlc(&buf[..len]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::ForwardedNPDU);
+        assert_eq!(bvlc.ip_port(), &Some(ip_port));
+        assert_eq!(bvlc.npdu().as_ref().unwrap().payload(), &[0x10, 0x08]);
+    }
+
+    #[test]
+    fn encode_register_foreign_device_test() {
+        let mut buf = [0u8; 6];
+        let len = encode_register_foreign_device(&mut buf, 300).unwrap();
+        assert_eq!(&buf[..len], &[0x81, 0x05, 0x00, 0x06, 0x01, 0x2c]);
+        let bvlc = parse_bvlc(&buf[..len]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::RegisterForeignDevice);
+        assert!(bvlc.npdu().is_none());
+    }
+
+    #[test]
+    fn encode_errors_test() {
+        let mut buf = [0u8; 11];
+        assert!(encode_unicast_npdu(&mut buf, NPDU).is_err());
+        assert!(encode_unicast_npdu(&mut buf, &NPDU[..7]).is_ok());
+        let ip_port = IpPort { ip: 0, port: 0 };
+        assert!(encode_bvlc(&mut buf, BVLCFunction::UnicastNPDU, Some(&ip_port), &[]).is_err());
+        assert!(encode_bvlc(&mut buf, BVLCFunction::ForwardedNPDU, None, &[]).is_err());
+        assert!(encode_bvlc(&mut buf, BVLCFunction::Unknown(0x0d), None, &[]).is_ok());
+        assert_eq!(&buf[..4], &[0x81, 0x0d, 0x00, 0x04]);
+    }
+
+    #[test]
+    fn bvlc_function_round_trip_test() {
+        for b in 0..=255u8 {
+            assert_eq!(u8::from(BVLCFunction::from(b)), b);
+        }
+        assert_eq!(BVLCFunction::from(0x0d), BVLCFunction::Unknown(0x0d));
+        assert_eq!(BVLCFunction::from(0xff), BVLCFunction::Unknown(0xff));
+    }
+
+    #[test]
+    fn bvlc_default_test() {
+        assert_eq!(BVLCFunction::default(), BVLCFunction::Unknown(0xff));
+        let bvlc = BVLC::default();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::Unknown(0xff));
+        assert!(!bvlc.has_npdu());
+        assert!(bvlc.result_code().is_none());
+        assert!(bvlc.ttl().is_none());
+        assert!(bvlc.ip_port().is_none());
+        assert!(bvlc.npdu().is_none());
+
+        // a default with a function that has a payload must not panic
+        let bvlc = BVLC {
+            bfn: BVLCFunction::BVLCResult,
+            ..Default::default()
+        };
+        assert!(bvlc.result_code().is_none());
+        let bvlc = BVLC {
+            bfn: BVLCFunction::RegisterForeignDevice,
+            ..Default::default()
+        };
+        assert!(bvlc.ttl().is_none());
+    }
+
+    #[test]
+    fn fdt_functions_test() {
+        let bvlc = parse_bvlc(&[0x81, 0x06, 0x00, 0x04]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::RFDT);
+        assert!(!bvlc.has_npdu());
+        assert!(bvlc.npdu().is_none());
+
+        let bvlc = parse_bvlc(&[0x81, 0x07, 0x00, 0x04]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::RFDTAck);
+
+        let bytes: &[u8] = &[0x81, 0x08, 0x00, 0x0a, 0xc0, 0xa8, 0x01, 0x02, 0xba, 0xc0];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::DeleteFDTEntry);
+        assert!(!bvlc.has_npdu());
+        assert_eq!(
+            bvlc.ip_port(),
+            &Some(IpPort {
+                ip: 0xc0a8_0102,
+                port: 0xbac0
+            })
+        );
+    }
+
+    #[test]
+    fn bvlc_result_test() {
+        let bvlc = parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x00]).unwrap();
+        assert_eq!(
+            bvlc.result_code(),
+            Some(BVLCResultCode::SuccessfulCompletion)
+        );
+        let bvlc = parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x30]).unwrap();
+        assert_eq!(
+            bvlc.result_code(),
+            Some(BVLCResultCode::RegisterForeignDeviceNAK)
+        );
+        let bvlc = parse_bvlc(&[0x81, 0x00, 0x00, 0x06, 0x00, 0x70]).unwrap();
+        assert_eq!(bvlc.result_code(), Some(BVLCResultCode::Unknown(0x70)));
+        assert!(bvlc.bdt_entries().is_none());
+        assert!(bvlc.ttl().is_none());
+        assert!(parse_bvlc(&[0x81, 0x00, 0x00, 0x05, 0x00]).is_err());
+        let bvlc = parse_bvlc(&[0x81, 0x0a, 0x00, 0x04]).unwrap();
+        assert!(bvlc.result_code().is_none());
+    }
+
+    #[test]
+    fn register_foreign_device_ttl_test() {
+        let bvlc = parse_bvlc(&[0x81, 0x05, 0x00, 0x06, 0x01, 0x2c]).unwrap();
+        assert_eq!(bvlc.ttl(), Some(300));
+        assert!(parse_bvlc(&[0x81, 0x05, 0x00, 0x05, 0x01]).is_err());
+    }
+
+    #[test]
+    fn bdt_entries_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x03, 0x00, 0x18, // BVLC
+            0xc0, 0xa8, 0x01, 0x01, 0xba, 0xc0, 0xff, 0xff, 0xff, 0xff, // first entry
+            0x0a, 0x00, 0x00, 0x01, 0xba, 0xc1, 0xff, 0xff, 0xff, 0x00, // second entry
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::RBDTAck);
+        let mut entries = bvlc.bdt_entries().unwrap();
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.ip_port.ip, 0xc0a8_0101);
+        assert_eq!(entry.ip_port.port, 0xbac0);
+        assert_eq!(entry.broadcast_mask, 0xffff_ffff);
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.ip_port.ip, 0x0a00_0001);
+        assert_eq!(entry.ip_port.port, 0xbac1);
+        assert_eq!(entry.broadcast_mask, 0xffff_ff00);
+        assert!(entries.next().is_none());
+
+        let mut wbdt = [0u8; 24];
+        wbdt.copy_from_slice(bytes);
+        wbdt[1] = 0x01;
+        let bvlc = parse_bvlc(&wbdt).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCFunction::WBDT);
+        assert_eq!(bvlc.bdt_entries().unwrap().count(), 2);
+
+        let bvlc = parse_bvlc(&[0x81, 0x03, 0x00, 0x04]).unwrap();
+        assert_eq!(bvlc.bdt_entries().unwrap().count(), 0);
+        assert!(parse_bvlc(&bytes[..23]).is_err());
+        assert!(parse_bvlc(&[0x81, 0x03, 0x00, 0x06, 0xc0, 0xa8]).is_err());
+    }
+
+    #[test]
+    fn fdt_entries_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x07, 0x00, 0x0e, // BVLC
+            0xc0, 0xa8, 0x01, 0x01, 0xba, 0xc0, 0x01, 0x2c, 0x00, 0x5a, // entry
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let mut entries = bvlc.fdt_entries().unwrap();
+        let entry = entries.next().unwrap();
+        assert_eq!(entry.ip_port.ip, 0xc0a8_0101);
+        assert_eq!(entry.ip_port.port, 0xbac0);
+        assert_eq!(entry.ttl, 300);
+        assert_eq!(entry.remaining, 90);
+        assert!(entries.next().is_none());
+        assert!(bvlc.bdt_entries().is_none());
+        assert!(parse_bvlc(&[0x81, 0x07, 0x00, 0x06, 0xc0, 0xa8]).is_err());
+    }
+
+    #[test]
+    fn bvlc_length_too_small_test() {
+        assert!(parse_bvlc(&[0x81, 0x0a, 0x00, 0x02, 0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn distribute_broadcast_to_network_test() {
+        let mut buf = [0u8; 32];
+        let len = encode_bvlc(
+            &mut buf,
+            BVLCFunction::DistributeBroadcastToNetwork,
+            None,
+            NPDU,
+        )
+        .unwrap();
+        assert_eq!(&buf[..4], &[0x81, 0x09, 0x00, 0x0c]);
+        let bvlc = parse_bvlc(&buf[..len]).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLCFunction::DistributeBroadcastToNetwork
+        );
+        assert!(bvlc.has_npdu());
+        assert!(!bvlc.has_ip_port());
+        assert_eq!(bvlc.npdu().as_ref().unwrap().payload(), &[0x10, 0x08]);
+    }
+}
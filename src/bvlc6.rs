@@ -0,0 +1,365 @@
+//! BACnet/IPv6 BVLL, as described in Annex U.
+use super::npdu::*;
+use super::Error;
+use arrayref::array_ref;
+
+pub fn parse_bvlc6(slice: &[u8]) -> Result<BVLC6<'_>, Error> {
+    if slice.len() < 4 {
+        return Err(Error::Length("insufficient size for bvlc6"));
+    }
+    if slice[0] != 0x82 {
+        return Err(Error::InvalidValue("invalid bvlc6 type"));
+    }
+
+    let len = u16::from_be_bytes(*array_ref!(slice, 2, 2));
+    let len = len as usize;
+    if len > slice.len() {
+        return Err(Error::Length("bvlc6 length too large"));
+    }
+    if len < 4 {
+        return Err(Error::Length("bvlc6 length too small"));
+    }
+    let slice = &slice[..len];
+
+    let mut bvlc = BVLC6 {
+        bfn: slice[1].into(),
+        src_vmac: None,
+        dst_vmac: None,
+        ip_port: None,
+        npdu: None,
+        payload: &[],
+    };
+    let (has_src_vmac, has_dst_vmac, has_ip_port) = match bvlc.bfn {
+        BVLC6Function::SecureBVLL | BVLC6Function::Unknown(_) => (false, false, false),
+        BVLC6Function::OriginalUnicastNPDU
+        | BVLC6Function::AddressResolution
+        | BVLC6Function::AddressResolutionAck
+        | BVLC6Function::VirtualAddressResolutionAck => (true, true, false),
+        BVLC6Function::ForwardedAddressResolution => (true, true, true),
+        BVLC6Function::ForwardedNPDU | BVLC6Function::DeleteFDTEntry => (true, false, true),
+        _ => (true, false, false),
+    };
+    let mut rest = &slice[4..];
+    if has_src_vmac {
+        let (r, vmac) = parse_vmac(rest)?;
+        bvlc.src_vmac = Some(vmac);
+        rest = r;
+    }
+    if has_dst_vmac {
+        let (r, vmac) = parse_vmac(rest)?;
+        bvlc.dst_vmac = Some(vmac);
+        rest = r;
+    }
+    if has_ip_port {
+        if rest.len() < 18 {
+            return Err(Error::Length("insufficient size for bvlc6 address"));
+        }
+        bvlc.ip_port = Some(array_ref!(rest, 0, 18).into());
+        rest = &rest[18..];
+    }
+    bvlc.payload = rest;
+    match bvlc.bfn {
+        BVLC6Function::BVLCResult if rest.len() < 2 => {
+            return Err(Error::Length("insufficient size for bvlc6 result"));
+        }
+        BVLC6Function::RegisterForeignDevice if rest.len() < 2 => {
+            return Err(Error::Length("insufficient size for foreign device ttl"));
+        }
+        _ => (),
+    }
+    if bvlc.has_npdu() {
+        if let Ok(npdu) = parse_npdu(rest) {
+            bvlc.npdu = Some(npdu);
+        }
+    }
+    Ok(bvlc)
+}
+
+/// A 3 byte BACnet/IPv6 virtual MAC address.
+pub type VMAC = [u8; 3];
+
+fn parse_vmac(b: &[u8]) -> Result<(&[u8], VMAC), Error> {
+    if b.len() < 3 {
+        return Err(Error::Length("insufficient size for vmac"));
+    }
+    Ok((&b[3..], *array_ref!(b, 0, 3)))
+}
+
+pub struct BVLC6<'a> {
+    bfn: BVLC6Function,
+    src_vmac: Option<VMAC>,
+    dst_vmac: Option<VMAC>,
+    ip_port: Option<Ipv6Port>,
+    npdu: Option<NPDU<'a>>,
+    payload: &'a [u8],
+}
+
+impl<'a> BVLC6<'a> {
+    pub fn bvlc_function(&self) -> BVLC6Function {
+        self.bfn
+    }
+    /// The virtual MAC address of the sender, or of the original source for a Forwarded-NPDU.
+    pub fn src_vmac(&self) -> Option<VMAC> {
+        self.src_vmac
+    }
+    /// The destination virtual MAC address, or the target virtual MAC address of an
+    /// (Forwarded-)Address-Resolution.
+    pub fn dst_vmac(&self) -> Option<VMAC> {
+        self.dst_vmac
+    }
+    /// The original source address of a Forwarded-NPDU or Forwarded-Address-Resolution, or the
+    /// entry to delete of a Delete-Foreign-Device-Table-Entry.
+    pub fn ip_port(&self) -> &Option<Ipv6Port> {
+        &self.ip_port
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+    pub fn has_npdu(&self) -> bool {
+        matches!(
+            &self.bfn,
+            BVLC6Function::OriginalUnicastNPDU
+                | BVLC6Function::OriginalBroadcastNPDU
+                | BVLC6Function::ForwardedNPDU
+                | BVLC6Function::DistributeBroadcastToNetwork
+        )
+    }
+    /// The result code of a BVLC-Result.
+    pub fn result_code(&self) -> Option<BVLC6ResultCode> {
+        match self.bfn {
+            BVLC6Function::BVLCResult => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)).into())
+            }
+            _ => None,
+        }
+    }
+    /// The time-to-live in seconds of a Register-Foreign-Device.
+    pub fn ttl(&self) -> Option<u16> {
+        match self.bfn {
+            BVLC6Function::RegisterForeignDevice => {
+                Some(u16::from_be_bytes(*array_ref!(self.payload, 0, 2)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLC6Function {
+    BVLCResult,
+    OriginalUnicastNPDU,
+    OriginalBroadcastNPDU,
+    AddressResolution,
+    ForwardedAddressResolution,
+    AddressResolutionAck,
+    VirtualAddressResolution,
+    VirtualAddressResolutionAck,
+    ForwardedNPDU,
+    RegisterForeignDevice,
+    DeleteFDTEntry,
+    SecureBVLL,
+    DistributeBroadcastToNetwork,
+    Unknown(u8),
+}
+
+impl From<u8> for BVLC6Function {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => Self::BVLCResult,
+            0x01 => Self::OriginalUnicastNPDU,
+            0x02 => Self::OriginalBroadcastNPDU,
+            0x03 => Self::AddressResolution,
+            0x04 => Self::ForwardedAddressResolution,
+            0x05 => Self::AddressResolutionAck,
+            0x06 => Self::VirtualAddressResolution,
+            0x07 => Self::VirtualAddressResolutionAck,
+            0x08 => Self::ForwardedNPDU,
+            0x09 => Self::RegisterForeignDevice,
+            0x0a => Self::DeleteFDTEntry,
+            0x0b => Self::SecureBVLL,
+            0x0c => Self::DistributeBroadcastToNetwork,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLC6ResultCode {
+    SuccessfulCompletion,
+    AddressResolutionNAK,
+    VirtualAddressResolutionNAK,
+    RegisterForeignDeviceNAK,
+    DeleteFDTEntryNAK,
+    DistributeBroadcastToNetworkNAK,
+    Unknown(u16),
+}
+
+impl From<u16> for BVLC6ResultCode {
+    fn from(b: u16) -> Self {
+        match b {
+            0x0000 => Self::SuccessfulCompletion,
+            0x0030 => Self::AddressResolutionNAK,
+            0x0060 => Self::VirtualAddressResolutionNAK,
+            0x0090 => Self::RegisterForeignDeviceNAK,
+            0x00a0 => Self::DeleteFDTEntryNAK,
+            0x00c0 => Self::DistributeBroadcastToNetworkNAK,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+/// A B/IPv6 address: an IPv6 address and UDP port.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ipv6Port {
+    pub ip: u128,
+    pub port: u16,
+}
+
+impl From<&[u8; 18]> for Ipv6Port {
+    fn from(b: &[u8; 18]) -> Ipv6Port {
+        Ipv6Port {
+            ip: u128::from_be_bytes(*array_ref!(b, 0, 16)),
+            port: u16::from_be_bytes(*array_ref!(b, 16, 2)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: [u8; 18] = [
+        0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0xba, 0xc0,
+    ];
+
+    #[test]
+    fn original_unicast_npdu_test() {
+        let bytes: &[u8] = &[
+            0x82, 0x01, 0x00, 0x0f, // BVLC6
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, // VMACs
+            0x01, 0x04, 0x00, 0x05, 0x01, // NPDU
+        ];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::OriginalUnicastNPDU);
+        assert_eq!(bvlc.src_vmac(), Some([0x01, 0x02, 0x03]));
+        assert_eq!(bvlc.dst_vmac(), Some([0x04, 0x05, 0x06]));
+        assert!(bvlc.ip_port().is_none());
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        assert!(npdu.is_expecting_reply());
+        assert_eq!(npdu.payload(), &[0x00, 0x05, 0x01]);
+    }
+
+    #[test]
+    fn original_broadcast_npdu_test() {
+        let bytes: &[u8] = &[0x82, 0x02, 0x00, 0x0a, 0x01, 0x02, 0x03, 0x01, 0x00, 0x10];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::OriginalBroadcastNPDU);
+        assert_eq!(bvlc.src_vmac(), Some([0x01, 0x02, 0x03]));
+        assert!(bvlc.dst_vmac().is_none());
+        assert!(bvlc.npdu().is_some());
+    }
+
+    #[test]
+    fn forwarded_npdu_test() {
+        let mut bytes = [0u8; 28];
+        bytes[..7].copy_from_slice(&[0x82, 0x08, 0x00, 0x1c, 0x0a, 0x0b, 0x0c]);
+        bytes[7..25].copy_from_slice(&ADDR);
+        bytes[25..].copy_from_slice(&[0x01, 0x00, 0x10]);
+        let bvlc = parse_bvlc6(&bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::ForwardedNPDU);
+        assert_eq!(bvlc.src_vmac(), Some([0x0a, 0x0b, 0x0c]));
+        let ip_port = bvlc.ip_port().as_ref().unwrap();
+        assert_eq!(ip_port.ip, 0xfe80_0000_0000_0000_0000_0000_0000_0001);
+        assert_eq!(ip_port.port, 0xbac0);
+        assert_eq!(bvlc.npdu().as_ref().unwrap().payload(), &[0x10]);
+
+        assert!(parse_bvlc6(&bytes[..20]).is_err());
+        bytes[3] = 0x14;
+        assert!(parse_bvlc6(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn address_resolution_test() {
+        let bytes: &[u8] = &[0x82, 0x03, 0x00, 0x0a, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::AddressResolution);
+        assert_eq!(bvlc.dst_vmac(), Some([0x04, 0x05, 0x06]));
+        assert!(!bvlc.has_npdu());
+
+        let mut bytes = [0u8; 28];
+        bytes[..10].copy_from_slice(&[0x82, 0x04, 0x00, 0x1c, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        bytes[10..].copy_from_slice(&ADDR);
+        let bvlc = parse_bvlc6(&bytes).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLC6Function::ForwardedAddressResolution
+        );
+        assert_eq!(bvlc.dst_vmac(), Some([0x04, 0x05, 0x06]));
+        assert_eq!(bvlc.ip_port().as_ref().unwrap().port, 0xbac0);
+
+        let bytes: &[u8] = &[0x82, 0x06, 0x00, 0x07, 0x01, 0x02, 0x03];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLC6Function::VirtualAddressResolution
+        );
+        assert_eq!(bvlc.src_vmac(), Some([0x01, 0x02, 0x03]));
+        assert!(bvlc.dst_vmac().is_none());
+
+        let bytes: &[u8] = &[0x82, 0x07, 0x00, 0x0a, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLC6Function::VirtualAddressResolutionAck
+        );
+        assert_eq!(bvlc.dst_vmac(), Some([0x04, 0x05, 0x06]));
+        assert!(parse_bvlc6(&bytes[..8]).is_err());
+    }
+
+    #[test]
+    fn result_and_foreign_device_test() {
+        let bytes: &[u8] = &[0x82, 0x00, 0x00, 0x09, 0x01, 0x02, 0x03, 0x00, 0x90];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(
+            bvlc.result_code(),
+            Some(BVLC6ResultCode::RegisterForeignDeviceNAK)
+        );
+        assert!(bvlc.ttl().is_none());
+
+        let bytes: &[u8] = &[0x82, 0x09, 0x00, 0x09, 0x01, 0x02, 0x03, 0x00, 0x3c];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(bvlc.ttl(), Some(60));
+        assert!(bvlc.result_code().is_none());
+        assert!(parse_bvlc6(&[0x82, 0x09, 0x00, 0x08, 0x01, 0x02, 0x03, 0x00]).is_err());
+
+        let mut bytes = [0u8; 25];
+        bytes[..7].copy_from_slice(&[0x82, 0x0a, 0x00, 0x19, 0x01, 0x02, 0x03]);
+        bytes[7..].copy_from_slice(&ADDR);
+        let bvlc = parse_bvlc6(&bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::DeleteFDTEntry);
+        assert_eq!(bvlc.ip_port().as_ref().unwrap().port, 0xbac0);
+    }
+
+    #[test]
+    fn distribute_broadcast_to_network_test() {
+        let bytes: &[u8] = &[0x82, 0x0c, 0x00, 0x0a, 0x01, 0x02, 0x03, 0x01, 0x00, 0x10];
+        let bvlc = parse_bvlc6(bytes).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLC6Function::DistributeBroadcastToNetwork
+        );
+        assert!(bvlc.has_npdu());
+        assert!(bvlc.npdu().is_some());
+    }
+
+    #[test]
+    fn invalid_bvlc6_test() {
+        assert!(parse_bvlc6(&[0x81, 0x0a, 0x00, 0x04]).is_err());
+        assert!(parse_bvlc6(&[0x82, 0x0a, 0x00]).is_err());
+        assert!(parse_bvlc6(&[0x82, 0x01, 0x00, 0x03]).is_err());
+        let bvlc = parse_bvlc6(&[0x82, 0x0d, 0x00, 0x04]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLC6Function::Unknown(0x0d));
+        assert!(bvlc.src_vmac().is_none());
+    }
+}
@@ -0,0 +1,592 @@
+//! BACnet Secure Connect BVLC, as described in Annex AB.
+use super::npdu::*;
+use super::nsdu::apdu::error::{ErrorClass, ErrorCode};
+use super::Error;
+use arrayref::array_ref;
+
+pub fn parse_bvlc_sc(slice: &[u8]) -> Result<BVLCSC<'_>, Error> {
+    if slice.len() < 4 {
+        return Err(Error::Length("insufficient size for bvlc-sc"));
+    }
+    let bfn: BVLCSCFunction = slice[0].into();
+    let control = slice[1];
+    let message_id = u16::from_be_bytes(*array_ref!(slice, 2, 2));
+    let mut rest = &slice[4..];
+
+    let src_vmac = if control & 0x08 != 0 {
+        let (r, vmac) = parse_vmac(rest)?;
+        rest = r;
+        Some(vmac)
+    } else {
+        None
+    };
+    let dst_vmac = if control & 0x04 != 0 {
+        let (r, vmac) = parse_vmac(rest)?;
+        rest = r;
+        Some(vmac)
+    } else {
+        None
+    };
+    let dst_options = if control & 0x02 != 0 {
+        let (r, options) = parse_header_options(rest)?;
+        rest = r;
+        options
+    } else {
+        &[]
+    };
+    let data_options = if control & 0x01 != 0 {
+        let (r, options) = parse_header_options(rest)?;
+        rest = r;
+        options
+    } else {
+        &[]
+    };
+
+    let payload = match bfn {
+        BVLCSCFunction::BVLCResult => SCPayload::Result(SCResult::parse(rest)?),
+        BVLCSCFunction::EncapsulatedNPDU => SCPayload::NPDU(rest),
+        BVLCSCFunction::AddressResolutionAck => SCPayload::AddressResolutionAck(
+            core::str::from_utf8(rest)
+                .map_err(|_| Error::InvalidValue("websocket uris are not utf-8"))?,
+        ),
+        BVLCSCFunction::Advertisement => SCPayload::Advertisement(Advertisement::parse(rest)?),
+        BVLCSCFunction::ConnectRequest | BVLCSCFunction::ConnectAccept => {
+            SCPayload::Connect(ConnectInfo::parse(rest)?)
+        }
+        BVLCSCFunction::ProprietaryMessage => {
+            SCPayload::Proprietary(ProprietaryMessage::parse(rest)?)
+        }
+        BVLCSCFunction::Unknown(_) => SCPayload::Unknown(rest),
+        _ => SCPayload::None,
+    };
+    let npdu = match payload {
+        SCPayload::NPDU(bytes) => parse_npdu(bytes).ok(),
+        _ => None,
+    };
+    Ok(BVLCSC {
+        bfn,
+        message_id,
+        src_vmac,
+        dst_vmac,
+        dst_options,
+        data_options,
+        payload,
+        npdu,
+    })
+}
+
+/// A 6 byte BACnet/SC virtual MAC address.
+pub type VMAC = [u8; 6];
+
+fn parse_vmac(b: &[u8]) -> Result<(&[u8], VMAC), Error> {
+    if b.len() < 6 {
+        return Err(Error::Length("insufficient size for vmac"));
+    }
+    Ok((&b[6..], *array_ref!(b, 0, 6)))
+}
+
+/// Returns a tuple of the bytes after the header options as well as the header options.
+fn parse_header_options(b: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let mut rest = b;
+    loop {
+        let (r, option) = HeaderOption::parse(rest)?;
+        rest = r;
+        if !option.more_options {
+            return Ok((rest, &b[..b.len() - rest.len()]));
+        }
+    }
+}
+
+pub struct BVLCSC<'a> {
+    bfn: BVLCSCFunction,
+    message_id: u16,
+    src_vmac: Option<VMAC>,
+    dst_vmac: Option<VMAC>,
+    dst_options: &'a [u8],
+    data_options: &'a [u8],
+    payload: SCPayload<'a>,
+    npdu: Option<NPDU<'a>>,
+}
+
+impl<'a> BVLCSC<'a> {
+    pub fn bvlc_function(&self) -> BVLCSCFunction {
+        self.bfn
+    }
+    pub fn message_id(&self) -> u16 {
+        self.message_id
+    }
+    /// The originating virtual address, if present.
+    pub fn src_vmac(&self) -> Option<VMAC> {
+        self.src_vmac
+    }
+    /// The destination virtual address, if present.
+    pub fn dst_vmac(&self) -> Option<VMAC> {
+        self.dst_vmac
+    }
+    pub fn destination_options(&self) -> HeaderOptions<'a> {
+        HeaderOptions {
+            bytes: self.dst_options,
+        }
+    }
+    pub fn data_options(&self) -> HeaderOptions<'a> {
+        HeaderOptions {
+            bytes: self.data_options,
+        }
+    }
+    pub fn payload(&self) -> SCPayload<'a> {
+        self.payload
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+    pub fn has_npdu(&self) -> bool {
+        matches!(&self.bfn, BVLCSCFunction::EncapsulatedNPDU)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BVLCSCFunction {
+    BVLCResult,
+    EncapsulatedNPDU,
+    AddressResolution,
+    AddressResolutionAck,
+    Advertisement,
+    AdvertisementSolicitation,
+    ConnectRequest,
+    ConnectAccept,
+    DisconnectRequest,
+    DisconnectAck,
+    HeartbeatRequest,
+    HeartbeatAck,
+    ProprietaryMessage,
+    Unknown(u8),
+}
+
+impl From<u8> for BVLCSCFunction {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => Self::BVLCResult,
+            0x01 => Self::EncapsulatedNPDU,
+            0x02 => Self::AddressResolution,
+            0x03 => Self::AddressResolutionAck,
+            0x04 => Self::Advertisement,
+            0x05 => Self::AdvertisementSolicitation,
+            0x06 => Self::ConnectRequest,
+            0x07 => Self::ConnectAccept,
+            0x08 => Self::DisconnectRequest,
+            0x09 => Self::DisconnectAck,
+            0x0a => Self::HeartbeatRequest,
+            0x0b => Self::HeartbeatAck,
+            0x0c => Self::ProprietaryMessage,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+/// The payload of a BACnet/SC BVLC message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SCPayload<'a> {
+    /// Functions without a payload.
+    None,
+    Result(SCResult<'a>),
+    /// The bytes of an Encapsulated-NPDU. The parsed NPDU is available from `BVLCSC::npdu()`.
+    NPDU(&'a [u8]),
+    /// The space separated WebSocket URIs of an Address-Resolution-ACK.
+    AddressResolutionAck(&'a str),
+    Advertisement(Advertisement),
+    /// The payload of a Connect-Request or Connect-Accept.
+    Connect(ConnectInfo),
+    Proprietary(ProprietaryMessage<'a>),
+    Unknown(&'a [u8]),
+}
+
+/// The payload of a BVLC-Result.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SCResult<'a> {
+    /// The function that this is the result for.
+    pub function: BVLCSCFunction,
+    /// `None` for an ACK.
+    pub nak: Option<SCNak<'a>>,
+}
+
+impl<'a> SCResult<'a> {
+    fn parse(b: &'a [u8]) -> Result<Self, Error> {
+        if b.len() < 2 {
+            return Err(Error::Length("insufficient size for bvlc-sc result"));
+        }
+        let nak = match b[1] {
+            0x00 => None,
+            0x01 => {
+                if b.len() < 7 {
+                    return Err(Error::Length("insufficient size for bvlc-sc nak"));
+                }
+                Some(SCNak {
+                    error_header_marker: b[2],
+                    error_class: u32::from(u16::from_be_bytes(*array_ref!(b, 3, 2))).into(),
+                    error_code: u32::from(u16::from_be_bytes(*array_ref!(b, 5, 2))).into(),
+                    error_details: &b[7..],
+                })
+            }
+            _ => return Err(Error::InvalidValue("invalid bvlc-sc result code")),
+        };
+        Ok(Self {
+            function: b[0].into(),
+            nak,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SCNak<'a> {
+    /// The marker of the header option that caused the error, or 0.
+    pub error_header_marker: u8,
+    pub error_class: ErrorClass,
+    pub error_code: ErrorCode,
+    /// UTF-8 encoded error details, which may be empty.
+    pub error_details: &'a [u8],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Advertisement {
+    pub hub_connection_status: HubConnectionStatus,
+    pub accepts_direct_connections: bool,
+    pub max_bvlc_length: u16,
+    pub max_npdu_length: u16,
+}
+
+impl Advertisement {
+    fn parse(b: &[u8]) -> Result<Self, Error> {
+        if b.len() < 6 {
+            return Err(Error::Length("insufficient size for advertisement"));
+        }
+        Ok(Self {
+            hub_connection_status: b[0].into(),
+            accepts_direct_connections: b[1] != 0,
+            max_bvlc_length: u16::from_be_bytes(*array_ref!(b, 2, 2)),
+            max_npdu_length: u16::from_be_bytes(*array_ref!(b, 4, 2)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HubConnectionStatus {
+    NoHubConnection,
+    ConnectedToPrimaryHub,
+    ConnectedToFailoverHub,
+    Unknown(u8),
+}
+
+impl From<u8> for HubConnectionStatus {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::NoHubConnection,
+            1 => Self::ConnectedToPrimaryHub,
+            2 => Self::ConnectedToFailoverHub,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConnectInfo {
+    pub vmac: VMAC,
+    pub device_uuid: [u8; 16],
+    pub max_bvlc_length: u16,
+    pub max_npdu_length: u16,
+}
+
+impl ConnectInfo {
+    fn parse(b: &[u8]) -> Result<Self, Error> {
+        if b.len() < 26 {
+            return Err(Error::Length("insufficient size for connect payload"));
+        }
+        Ok(Self {
+            vmac: *array_ref!(b, 0, 6),
+            device_uuid: *array_ref!(b, 6, 16),
+            max_bvlc_length: u16::from_be_bytes(*array_ref!(b, 22, 2)),
+            max_npdu_length: u16::from_be_bytes(*array_ref!(b, 24, 2)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProprietaryMessage<'a> {
+    pub vendor_id: u16,
+    pub function: u8,
+    pub data: &'a [u8],
+}
+
+impl<'a> ProprietaryMessage<'a> {
+    fn parse(b: &'a [u8]) -> Result<Self, Error> {
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for proprietary message"));
+        }
+        Ok(Self {
+            vendor_id: u16::from_be_bytes(*array_ref!(b, 0, 2)),
+            function: b[2],
+            data: &b[3..],
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeaderOption<'a> {
+    more_options: bool,
+    pub must_understand: bool,
+    pub option_type: HeaderOptionType,
+    /// The header data, if the header data flag is set.
+    pub data: Option<&'a [u8]>,
+}
+
+impl<'a> HeaderOption<'a> {
+    fn parse(b: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if b.is_empty() {
+            return Err(Error::Length("insufficient size for header option"));
+        }
+        let marker = b[0];
+        let (rest, data) = if marker & 0x20 != 0 {
+            if b.len() < 3 {
+                return Err(Error::Length("insufficient size for header option length"));
+            }
+            let len = u16::from_be_bytes(*array_ref!(b, 1, 2)) as usize;
+            if b.len() < 3 + len {
+                return Err(Error::Length("insufficient size for header option data"));
+            }
+            (&b[3 + len..], Some(&b[3..3 + len]))
+        } else {
+            (&b[1..], None)
+        };
+        Ok((
+            rest,
+            Self {
+                more_options: marker & 0x80 != 0,
+                must_understand: marker & 0x40 != 0,
+                option_type: (marker & 0x1f).into(),
+                data,
+            },
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HeaderOptionType {
+    SecurePath,
+    Proprietary,
+    Unknown(u8),
+}
+
+impl From<u8> for HeaderOptionType {
+    fn from(b: u8) -> Self {
+        match b {
+            1 => Self::SecurePath,
+            31 => Self::Proprietary,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+/// Iterates over a list of header options that has been validated by `parse_bvlc_sc`.
+pub struct HeaderOptions<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for HeaderOptions<'a> {
+    type Item = HeaderOption<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rest, option) = HeaderOption::parse(self.bytes).ok()?;
+        self.bytes = rest;
+        Some(option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulated_npdu_test() {
+        let bytes: &[u8] = &[
+            0x01, 0x09, 0x12, 0x34, // function, control, message id
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, // originating vmac
+            0x01, // data option: secure path
+            0x01, 0x04, 0x00, 0x05, 0x01, // NPDU
+        ];
+        let bvlc = parse_bvlc_sc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::EncapsulatedNPDU);
+        assert_eq!(bvlc.message_id(), 0x1234);
+        assert_eq!(bvlc.src_vmac(), Some([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]));
+        assert!(bvlc.dst_vmac().is_none());
+        assert_eq!(bvlc.destination_options().count(), 0);
+        let mut options = bvlc.data_options();
+        let option = options.next().unwrap();
+        assert_eq!(option.option_type, HeaderOptionType::SecurePath);
+        assert!(!option.must_understand);
+        assert!(option.data.is_none());
+        assert!(options.next().is_none());
+        assert!(bvlc.has_npdu());
+        assert_eq!(bvlc.payload(), SCPayload::NPDU(&bytes[11..]));
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        assert_eq!(npdu.payload(), &[0x00, 0x05, 0x01]);
+    }
+
+    #[test]
+    fn header_options_test() {
+        let bytes: &[u8] = &[
+            0x0a, 0x06, 0x00, 0x01, // heartbeat request
+            0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, // destination vmac
+            0xff, 0x00, 0x04, 0x01, 0x02, 0x03, 0x04, // proprietary, must understand, more
+            0x41, // secure path, must understand
+        ];
+        let bvlc = parse_bvlc_sc(bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::HeartbeatRequest);
+        assert_eq!(bvlc.dst_vmac(), Some([0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]));
+        let mut options = bvlc.destination_options();
+        let option = options.next().unwrap();
+        assert_eq!(option.option_type, HeaderOptionType::Proprietary);
+        assert!(option.must_understand);
+        assert_eq!(option.data, Some(&[0x01, 0x02, 0x03, 0x04][..]));
+        let option = options.next().unwrap();
+        assert_eq!(option.option_type, HeaderOptionType::SecurePath);
+        assert!(options.next().is_none());
+        assert_eq!(bvlc.payload(), SCPayload::None);
+        assert!(bvlc.npdu().is_none());
+
+        // more options flag set on the last option
+        assert!(parse_bvlc_sc(&bytes[..17]).is_err());
+        // option data shorter than its length
+        assert!(parse_bvlc_sc(&bytes[..14]).is_err());
+        // missing vmac
+        assert!(parse_bvlc_sc(&bytes[..8]).is_err());
+    }
+
+    #[test]
+    fn result_test() {
+        let bvlc = parse_bvlc_sc(&[0x00, 0x00, 0x00, 0x02, 0x06, 0x00]).unwrap();
+        assert_eq!(
+            bvlc.payload(),
+            SCPayload::Result(SCResult {
+                function: BVLCSCFunction::ConnectRequest,
+                nak: None,
+            })
+        );
+
+        let bytes: &[u8] = &[
+            0x00, 0x00, 0x00, 0x03, 0x06, 0x01, 0x00, 0x00, 0x07, 0x00, 0x97, 0x68, 0x69,
+        ];
+        match parse_bvlc_sc(bytes).unwrap().payload() {
+            SCPayload::Result(result) => {
+                assert_eq!(result.function, BVLCSCFunction::ConnectRequest);
+                let nak = result.nak.unwrap();
+                assert_eq!(nak.error_header_marker, 0);
+                assert_eq!(nak.error_class, ErrorClass::Communication);
+                assert_eq!(nak.error_code, ErrorCode::NodeDuplicateVMAC);
+                assert_eq!(nak.error_details, b"hi");
+            }
+            _ => panic!("should be a result"),
+        }
+        assert!(parse_bvlc_sc(&bytes[..10]).is_err());
+        assert!(parse_bvlc_sc(&[0x00, 0x00, 0x00, 0x03, 0x06, 0x02]).is_err());
+    }
+
+    #[test]
+    fn address_resolution_ack_test() {
+        let mut bytes = [0u8; 4 + 24];
+        bytes[..4].copy_from_slice(&[0x03, 0x00, 0x00, 0x04]);
+        bytes[4..].copy_from_slice(b"wss://a:443 wss://b:4443");
+        let bvlc = parse_bvlc_sc(&bytes).unwrap();
+        match bvlc.payload() {
+            SCPayload::AddressResolutionAck(uris) => {
+                let mut uris = uris.split(' ');
+                assert_eq!(uris.next(), Some("wss://a:443"));
+                assert_eq!(uris.next(), Some("wss://b:4443"));
+            }
+            _ => panic!("should be an address resolution ack"),
+        }
+        bytes[4] = 0xff;
+        assert!(parse_bvlc_sc(&bytes).is_err());
+    }
+
+    #[test]
+    fn advertisement_test() {
+        let bytes: &[u8] = &[0x04, 0x00, 0x00, 0x05, 0x01, 0x01, 0x06, 0x40, 0x05, 0xdc];
+        let bvlc = parse_bvlc_sc(bytes).unwrap();
+        assert_eq!(
+            bvlc.payload(),
+            SCPayload::Advertisement(Advertisement {
+                hub_connection_status: HubConnectionStatus::ConnectedToPrimaryHub,
+                accepts_direct_connections: true,
+                max_bvlc_length: 1600,
+                max_npdu_length: 1500,
+            })
+        );
+        assert!(parse_bvlc_sc(&bytes[..9]).is_err());
+        let bvlc = parse_bvlc_sc(&[0x05, 0x00, 0x00, 0x06]).unwrap();
+        assert_eq!(
+            bvlc.bvlc_function(),
+            BVLCSCFunction::AdvertisementSolicitation
+        );
+        assert_eq!(bvlc.payload(), SCPayload::None);
+    }
+
+    #[test]
+    fn connect_test() {
+        let mut bytes = [0u8; 30];
+        bytes[..4].copy_from_slice(&[0x06, 0x00, 0x00, 0x07]);
+        bytes[4..10].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        for (i, b) in bytes[10..26].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        bytes[26..].copy_from_slice(&[0x06, 0x40, 0x05, 0xdc]);
+        let bvlc = parse_bvlc_sc(&bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::ConnectRequest);
+        match bvlc.payload() {
+            SCPayload::Connect(connect) => {
+                assert_eq!(connect.vmac, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+                assert_eq!(connect.device_uuid[15], 15);
+                assert_eq!(connect.max_bvlc_length, 1600);
+                assert_eq!(connect.max_npdu_length, 1500);
+            }
+            _ => panic!("should be a connect payload"),
+        }
+        bytes[0] = 0x07;
+        let bvlc = parse_bvlc_sc(&bytes).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::ConnectAccept);
+        assert!(parse_bvlc_sc(&bytes[..29]).is_err());
+    }
+
+    #[test]
+    fn no_payload_functions_test() {
+        for (b, bfn) in &[
+            (0x02, BVLCSCFunction::AddressResolution),
+            (0x08, BVLCSCFunction::DisconnectRequest),
+            (0x09, BVLCSCFunction::DisconnectAck),
+            (0x0a, BVLCSCFunction::HeartbeatRequest),
+            (0x0b, BVLCSCFunction::HeartbeatAck),
+        ] {
+            let bytes = [*b, 0x00, 0x00, 0x01];
+            let bvlc = parse_bvlc_sc(&bytes).unwrap();
+            assert_eq!(bvlc.bvlc_function(), *bfn);
+            assert_eq!(bvlc.payload(), SCPayload::None);
+            assert!(!bvlc.has_npdu());
+        }
+    }
+
+    #[test]
+    fn proprietary_message_test() {
+        let bytes: &[u8] = &[0x0c, 0x00, 0x00, 0x08, 0x01, 0x04, 0x02, 0xaa, 0xbb];
+        let bvlc = parse_bvlc_sc(bytes).unwrap();
+        assert_eq!(
+            bvlc.payload(),
+            SCPayload::Proprietary(ProprietaryMessage {
+                vendor_id: 260,
+                function: 2,
+                data: &[0xaa, 0xbb],
+            })
+        );
+        assert!(parse_bvlc_sc(&bytes[..6]).is_err());
+
+        let bvlc = parse_bvlc_sc(&[0x0d, 0x00, 0x00, 0x09, 0x01]).unwrap();
+        assert_eq!(bvlc.bvlc_function(), BVLCSCFunction::Unknown(0x0d));
+        assert_eq!(bvlc.payload(), SCPayload::Unknown(&[0x01]));
+    }
+}
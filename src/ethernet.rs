@@ -0,0 +1,163 @@
+//! BACnet/Ethernet, i.e. ISO 8802-3 frames with an 8802-2 LLC header, as described in Clause 7.
+use super::npdu::*;
+use super::Error;
+use arrayref::array_ref;
+use core::convert::From;
+
+/// Parses an 802.3 frame starting at the destination MAC address. The frame check sequence must
+/// not be included. Any padding after the length given in the frame's length field is ignored.
+pub fn parse_ethernet(bytes: &[u8]) -> Result<EthernetFrame<'_>, Error> {
+    // 17 comes from (dst = 6) + (src = 6) + (length = 2) + (llc = 3)
+    if bytes.len() < 17 {
+        return Err(Error::Length("insufficient size for bacnet ethernet frame"));
+    }
+    let len = u16::from_be_bytes(*array_ref!(bytes, 12, 2)) as usize;
+    if len > 1500 {
+        return Err(Error::InvalidValue("length field is an ethertype"));
+    }
+    if len < 3 {
+        return Err(Error::Length("length field is too small for llc header"));
+    }
+    if 14 + len > bytes.len() {
+        return Err(Error::Length("length field exceeds frame size"));
+    }
+    if bytes[14] != 0x82 || bytes[15] != 0x82 {
+        return Err(Error::InvalidValue("llc sap is not bacnet"));
+    }
+    let mut frame = EthernetFrame {
+        dst_mac: *array_ref!(bytes, 0, 6),
+        src_mac: *array_ref!(bytes, 6, 6),
+        llc_control: bytes[16].into(),
+        npdu: None,
+    };
+    if frame.llc_control() != LLCControl::UI {
+        return Ok(frame);
+    }
+    if let Ok(npdu) = parse_npdu(&bytes[17..14 + len]) {
+        frame.npdu = Some(npdu);
+    }
+    Ok(frame)
+}
+
+pub type MAC = [u8; 6];
+
+pub struct EthernetFrame<'a> {
+    dst_mac: MAC,
+    src_mac: MAC,
+    llc_control: LLCControl,
+    npdu: Option<NPDU<'a>>,
+}
+
+impl<'a> EthernetFrame<'a> {
+    pub fn dst_mac(&self) -> MAC {
+        self.dst_mac
+    }
+    pub fn src_mac(&self) -> MAC {
+        self.src_mac
+    }
+    pub fn llc_control(&self) -> LLCControl {
+        self.llc_control
+    }
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+}
+
+/// The LLC type 1 commands and responses, ignoring the poll/final bit. Shared with ARCNET, which
+/// uses the same 8802-2 LLC header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LLCControl {
+    UI,
+    XID,
+    Test,
+    Unknown(u8),
+}
+
+impl From<u8> for LLCControl {
+    fn from(b: u8) -> Self {
+        match b & !0x10 {
+            0x03 => Self::UI,
+            0xAF => Self::XID,
+            0xE3 => Self::Test,
+            _ => Self::Unknown(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: &[u8] = &[
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // dst
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, // src
+        0x00, 0x08, // length
+        0x82, 0x82, 0x03, // llc
+        0x01, 0x00, 0x10, 0x08, 0x00, // NPDU
+    ];
+
+    #[test]
+    fn ethernet_test() {
+        let frame = parse_ethernet(FRAME).unwrap();
+        assert_eq!(frame.dst_mac(), [0xff; 6]);
+        assert_eq!(frame.src_mac(), [0x00, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(frame.llc_control(), LLCControl::UI);
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert!(npdu.is_apdu());
+        assert_eq!(npdu.payload(), &[0x10, 0x08, 0x00]);
+    }
+
+    #[test]
+    fn ethernet_padding_test() {
+        let mut bytes = [0u8; 60];
+        bytes[..FRAME.len()].copy_from_slice(FRAME);
+        let frame = parse_ethernet(&bytes).unwrap();
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert_eq!(npdu.payload(), &[0x10, 0x08, 0x00]);
+    }
+
+    #[test]
+    fn ethernet_no_npdu_test() {
+        let mut bytes = [0u8; 22];
+        bytes.copy_from_slice(FRAME);
+        // TEST command
+        bytes[16] = 0xf3;
+        let frame = parse_ethernet(&bytes).unwrap();
+        assert_eq!(frame.llc_control(), LLCControl::Test);
+        assert!(frame.npdu().is_none());
+
+        // unknown control field
+        bytes[16] = 0x7f;
+        let frame = parse_ethernet(&bytes).unwrap();
+        assert_eq!(frame.llc_control(), LLCControl::Unknown(0x7f));
+        assert!(frame.npdu().is_none());
+
+        // unhandled npdu version
+        bytes[16] = 0x03;
+        bytes[17] = 0x02;
+        let frame = parse_ethernet(&bytes).unwrap();
+        assert_eq!(frame.llc_control(), LLCControl::UI);
+        assert!(frame.npdu().is_none());
+    }
+
+    #[test]
+    fn ethernet_invalid_test() {
+        assert!(parse_ethernet(&FRAME[..16]).is_err());
+        // length field larger than the frame
+        assert!(parse_ethernet(&FRAME[..21]).is_err());
+
+        let mut bytes = [0u8; 22];
+        bytes.copy_from_slice(FRAME);
+        // ethertype instead of length
+        bytes[12] = 0x08;
+        assert!(parse_ethernet(&bytes).is_err());
+        bytes[12] = 0x00;
+        bytes[13] = 0x02;
+        assert!(parse_ethernet(&bytes).is_err());
+        bytes[13] = 0x08;
+        bytes[15] = 0xaa;
+        assert!(parse_ethernet(&bytes).is_err());
+        bytes[15] = 0x82;
+        assert!(parse_ethernet(&bytes).is_ok());
+    }
+}
@@ -0,0 +1,218 @@
+//! BACnet/IP straight from the wire: Ethernet II, IPv4 and UDP headers followed by the BVLL.
+//!
+//! Requires the `ip` feature.
+use super::bvlc::*;
+use super::Error;
+use arrayref::array_ref;
+use core::ops::RangeInclusive;
+
+/// The UDP ports that BACnet/IP uses by default, i.e. 0xBAC0 to 0xBACF.
+pub const DEFAULT_PORTS: RangeInclusive<u16> = 0xBAC0..=0xBACF;
+
+/// Same as `parse_ethernet_ip_with_ports` with `DEFAULT_PORTS`.
+pub fn parse_ethernet_ip(frame: &[u8]) -> Result<IpFrame<'_>, Error> {
+    parse_ethernet_ip_with_ports(frame, DEFAULT_PORTS)
+}
+
+/// Parses an Ethernet II frame, optionally VLAN tagged, containing an unfragmented IPv4 UDP
+/// datagram with the BVLL as its payload. The frame check sequence must not be included. Either
+/// the UDP source or destination port must be within `ports`. IP and UDP checksums are not
+/// verified.
+pub fn parse_ethernet_ip_with_ports(
+    frame: &[u8],
+    ports: RangeInclusive<u16>,
+) -> Result<IpFrame<'_>, Error> {
+    if frame.len() < 14 {
+        return Err(Error::Length("insufficient size for ethernet header"));
+    }
+    let mut ethertype = u16::from_be_bytes(*array_ref!(frame, 12, 2));
+    let mut rest = &frame[14..];
+    let vlan_id = if ethertype == 0x8100 {
+        if rest.len() < 4 {
+            return Err(Error::Length("insufficient size for vlan tag"));
+        }
+        let tci = u16::from_be_bytes(*array_ref!(rest, 0, 2));
+        ethertype = u16::from_be_bytes(*array_ref!(rest, 2, 2));
+        rest = &rest[4..];
+        Some(tci & 0x0fff)
+    } else {
+        None
+    };
+    if ethertype != 0x0800 {
+        return Err(Error::InvalidValue("ethertype is not ipv4"));
+    }
+
+    let (udp, src_ip, dst_ip) = parse_ipv4(rest)?;
+
+    if udp.len() < 8 {
+        return Err(Error::Length("insufficient size for udp header"));
+    }
+    let src_port = u16::from_be_bytes(*array_ref!(udp, 0, 2));
+    let dst_port = u16::from_be_bytes(*array_ref!(udp, 2, 2));
+    let udp_len = u16::from_be_bytes(*array_ref!(udp, 4, 2)) as usize;
+    if udp_len < 8 || udp_len > udp.len() {
+        return Err(Error::Length("invalid udp length"));
+    }
+    if !ports.contains(&src_port) && !ports.contains(&dst_port) {
+        return Err(Error::InvalidValue("udp ports are not bacnet ports"));
+    }
+
+    Ok(IpFrame {
+        vlan_id,
+        src: IpPort {
+            ip: src_ip,
+            port: src_port,
+        },
+        dst: IpPort {
+            ip: dst_ip,
+            port: dst_port,
+        },
+        bvlc: parse_bvlc(&udp[8..udp_len])?,
+    })
+}
+
+/// Returns a tuple of the UDP datagram bytes as well as the source and destination IP addresses.
+fn parse_ipv4(b: &[u8]) -> Result<(&[u8], u32, u32), Error> {
+    if b.len() < 20 {
+        return Err(Error::Length("insufficient size for ipv4 header"));
+    }
+    if b[0] >> 4 != 4 {
+        return Err(Error::InvalidValue("ip version is not 4"));
+    }
+    let header_len = (b[0] & 0x0f) as usize * 4;
+    let total_len = u16::from_be_bytes(*array_ref!(b, 2, 2)) as usize;
+    if header_len < 20 || total_len < header_len || total_len > b.len() {
+        return Err(Error::Length("invalid ipv4 length"));
+    }
+    // more fragments flag or a non-zero fragment offset
+    if u16::from_be_bytes(*array_ref!(b, 6, 2)) & 0x3fff != 0 {
+        return Err(Error::InvalidValue("fragmented ipv4 packet"));
+    }
+    if b[9] != 17 {
+        return Err(Error::InvalidValue("ip protocol is not udp"));
+    }
+    Ok((
+        &b[header_len..total_len],
+        u32::from_be_bytes(*array_ref!(b, 12, 4)),
+        u32::from_be_bytes(*array_ref!(b, 16, 4)),
+    ))
+}
+
+pub struct IpFrame<'a> {
+    vlan_id: Option<u16>,
+    src: IpPort,
+    dst: IpPort,
+    bvlc: BVLC<'a>,
+}
+
+impl<'a> IpFrame<'a> {
+    pub fn vlan_id(&self) -> Option<u16> {
+        self.vlan_id
+    }
+    pub fn src(&self) -> IpPort {
+        self.src
+    }
+    pub fn dst(&self) -> IpPort {
+        self.dst
+    }
+    pub fn bvlc(&self) -> &BVLC<'a> {
+        &self.bvlc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: &[u8] = &[
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // dst
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, // src
+        0x08, 0x00, // ethertype
+        0x45, 0x00, 0x00, 0x24, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, // ipv4
+        192, 168, 1, 10, // src ip
+        192, 168, 1, 255, // dst ip
+        0xba, 0xc0, 0xba, 0xc0, 0x00, 0x10, 0x00, 0x00, // udp
+        0x81, 0x0b, 0x00, 0x08, // BVLC
+        0x01, 0x00, 0x10, 0x08, // NPDU
+    ];
+
+    fn check(frame: &IpFrame) {
+        assert_eq!(
+            frame.src(),
+            IpPort {
+                ip: 0xc0a8010a,
+                port: 0xbac0
+            }
+        );
+        assert_eq!(
+            frame.dst(),
+            IpPort {
+                ip: 0xc0a801ff,
+                port: 0xbac0
+            }
+        );
+        assert_eq!(frame.bvlc().bvlc_function(), BVLCFunction::BroadcastNPDU);
+        let npdu = frame.bvlc().npdu().as_ref().unwrap();
+        assert_eq!(npdu.payload(), &[0x10, 0x08]);
+    }
+
+    #[test]
+    fn ethernet_ip_test() {
+        let frame = parse_ethernet_ip(FRAME).unwrap();
+        assert!(frame.vlan_id().is_none());
+        check(&frame);
+
+        // padded to the minimum ethernet frame size
+        let mut bytes = [0u8; 60];
+        bytes[..FRAME.len()].copy_from_slice(FRAME);
+        check(&parse_ethernet_ip(&bytes).unwrap());
+    }
+
+    #[test]
+    fn ethernet_ip_vlan_test() {
+        let mut bytes = [0u8; 54];
+        bytes[..12].copy_from_slice(&FRAME[..12]);
+        bytes[12..16].copy_from_slice(&[0x81, 0x00, 0x20, 0x64]);
+        bytes[16..].copy_from_slice(&FRAME[12..]);
+        let frame = parse_ethernet_ip(&bytes).unwrap();
+        assert_eq!(frame.vlan_id(), Some(100));
+        check(&frame);
+    }
+
+    #[test]
+    fn ethernet_ip_ports_test() {
+        let mut bytes = [0u8; 50];
+        bytes.copy_from_slice(FRAME);
+        // ephemeral source port
+        bytes[34] = 0xc0;
+        assert!(parse_ethernet_ip(&bytes).is_ok());
+        bytes[36] = 0xc0;
+        assert!(parse_ethernet_ip(&bytes).is_err());
+        assert!(parse_ethernet_ip_with_ports(&bytes, 0xc0c0..=0xc0c0).is_ok());
+    }
+
+    #[test]
+    fn ethernet_ip_invalid_test() {
+        for len in 0..FRAME.len() {
+            assert!(parse_ethernet_ip(&FRAME[..len]).is_err());
+        }
+        let mut bytes = [0u8; 50];
+        bytes.copy_from_slice(FRAME);
+        // ipv6 ethertype
+        bytes[12] = 0x86;
+        bytes[13] = 0xdd;
+        assert!(parse_ethernet_ip(&bytes).is_err());
+        bytes.copy_from_slice(FRAME);
+        // more fragments
+        bytes[20] = 0x20;
+        assert!(parse_ethernet_ip(&bytes).is_err());
+        bytes.copy_from_slice(FRAME);
+        // tcp
+        bytes[23] = 6;
+        assert!(parse_ethernet_ip(&bytes).is_err());
+        bytes.copy_from_slice(FRAME);
+        // udp length shorter than its header
+        bytes[39] = 0x04;
+        assert!(parse_ethernet_ip(&bytes).is_err());
+    }
+}
@@ -5,27 +5,44 @@
 //!
 //! Currently handles:
 //! * MS/TP
-//! * BVLL (basic - just enough to get NPDU)
+//! * BACnet/Ethernet (802.3 with LLC)
+//! * ARCNET
+//! * PTP
+//! * BVLL (BACnet/IP, including BDT and FDT payloads)
+//! * BACnet/IPv6 BVLL
+//! * BACnet/SC BVLC
 //! * NPDU
 //!
 //! Targeting support for:
 //! * NSDU ([NLM/RPDU](http://www.bacnetwiki.com/wiki/index.php?title=Network_Layer_Message_Type), APDU)
 //!
-//! To assist parsing BACnet IP or BACnet Ethernet, two recommended libraries are:
+//! To assist parsing BACnet IP, two recommended libraries are:
 //! * [pnet](https://crates.io/crates/pnet)
 //! * [etherparse](https://crates.io/crates/etherparse)
 //!
 //! ## How to use this library
 //!
-//! For BACnet ethernet and BACnet IP, first identify your BACnet application layer bytes then call
-//! to `parse_bvlc(bytes)` and go from there.
+//! For BACnet IP, first identify your BACnet application layer bytes then call
+//! to `parse_bvlc(bytes)` and go from there. Alternatively, enable the `ip` feature and call
+//! `parse_ethernet_ip(bytes)` on the raw Ethernet II frame.
+//! For BACnet/IPv6, call `parse_bvlc6(bytes)` instead.
+//! For BACnet/SC, call `parse_bvlc_sc(bytes)` on the WebSocket binary message payload.
+//!
+//! For BACnet ethernet, call `parse_ethernet(bytes)` on the 802.3 frame, excluding the FCS.
 //!
 //! For MSTP, call either `parse_mstp(bytes)` or `parse_mstp_skip_crc_compute(bytes)`.
 //!
-//! Not yet implemented below:
+//! For ARCNET, call `parse_arcnet(bytes)` on the frame starting at the source MAC address.
+//!
+//! For PTP, call `parse_ptp_escaped(bytes, buf)` on the received frame, or `parse_ptp(bytes)` if the
+//! DLE escaping has already been removed.
 //!
 //! In order to parse the RPDU or APDU, first check which one you have with `npdu.is_apdu()` then
-//! call either `parse_apdu(npdu.payload())` or `parse_rpdu(npdu.payload())`.
+//! call either `parse_apdu(npdu.payload())` or `parse_rpdu(npdu.payload())`. The service requests and
+//! acknowledgements of the APDU can then be decoded with the views in `nsdu::apdu`, such as
+//! `ReadPropertyRequest`, `ReadAccessSpecifications`, `WritePropertyRequest` and `COVNotification`.
+//! Failed requests are described by `ErrorPDU`, `RejectPDU` and `AbortPDU`, and property values are
+//! decoded with `ApplicationValue`.
 //!
 //! ## Examples
 //!
@@ -130,6 +147,7 @@
 //! These are opinions, so if you disagree and would like to use nom for parsing, feel free to make
 //! a pull request that includes nom.
 #![no_std]
+#![forbid(unsafe_code)]
 
 pub mod mstp;
 pub use mstp::{parse_mstp, parse_mstp_skip_crc_compute};
@@ -137,6 +155,26 @@ pub use mstp::{parse_mstp, parse_mstp_skip_crc_compute};
 pub mod bvlc;
 pub use bvlc::parse_bvlc;
 
+pub mod bvlc6;
+pub use bvlc6::parse_bvlc6;
+
+pub mod bvlc_sc;
+pub use bvlc_sc::parse_bvlc_sc;
+
+pub mod ethernet;
+pub use ethernet::parse_ethernet;
+
+pub mod arcnet;
+pub use arcnet::parse_arcnet;
+
+pub mod ptp;
+pub use ptp::{parse_ptp, parse_ptp_escaped};
+
+#[cfg(feature = "ip")]
+pub mod ip;
+#[cfg(feature = "ip")]
+pub use ip::{parse_ethernet_ip, parse_ethernet_ip_with_ports};
+
 pub mod npdu;
 
 pub mod nsdu;
@@ -158,8 +196,16 @@ impl From<()> for Error {
 #[cfg(test)]
 pub mod test {
     use super::bvlc::*;
+    use super::mstp::*;
+    use super::npdu::*;
+    use super::nsdu::apdu::unconfirmed_request_pdu::UnconfirmedServiceChoice;
+    use super::nsdu::apdu::*;
+    use super::nsdu::object_type::ObjectType;
+    use super::nsdu::property_id::PropertyId;
+    use super::nsdu::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn simple_test() {
         let bytes: &[u8] = &[
             0x81, 0x0a, 0x00, 0x1b, // BVLC
@@ -168,7 +214,7 @@ pub mod test {
             0x21, 0x3f, // APDU
         ];
 
-        let bvlc = parse_bvlc(&bytes).unwrap();
+        let bvlc = parse_bvlc(bytes).unwrap();
         assert_eq!(bvlc.bvlc_function(), BVLCFunction::UnicastNPDU);
 
         let npdu = bvlc.npdu().as_ref().unwrap();
@@ -188,4 +234,66 @@ pub mod test {
         assert_eq!(dst.addr().len(), 1);
         assert_eq!(dst.addr()[0], 61);
     }
+
+    #[test]
+    fn truncated_input_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x0a, 0x00, 0x1b, // BVLC
+            0x01, 0x20, 0x00, 0x0d, 0x01, 0x3d, 0xff, // NPDU
+            0x30, 0xc9, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x6f, 0x19, 0x4c, 0x29, 0x00, 0x3e, 0x21,
+            0x21, 0x3f, // APDU
+        ];
+        // every prefix and suffix must be rejected or parsed, never panic
+        for i in 0..=bytes.len() {
+            for b in &[&bytes[..i], &bytes[i..]] {
+                let _ = parse_bvlc(b);
+                let _ = parse_mstp(b);
+                let _ = parse_npdu(b);
+                let _ = parse_rpdu(b);
+                if let Ok(apdu) = parse_apdu(b) {
+                    let _ = ComplexACKPDU::parse(&apdu);
+                    let _ = ErrorPDU::parse(&apdu);
+                    let _ = UnconfirmedServiceChoice::parse(&apdu);
+                }
+            }
+        }
+        assert!(parse_bvlc(&bytes[..3]).is_err());
+        assert!(parse_npdu(&bytes[4..6]).is_err());
+        assert!(parse_apdu(&[]).is_err());
+        assert!(parse_rpdu(&[]).is_err());
+    }
+
+    #[test]
+    fn short_mstp_test() {
+        assert!(parse_mstp_skip_crc_compute(&[]).is_err());
+        assert!(parse_mstp_skip_crc_compute(&[0x55]).is_err());
+        assert!(parse_mstp(&[0x55, 0xff, 0x00, 0x01, 0x02, 0x00, 0x00]).is_err());
+        assert!(parse_mstp(&[0x55, 0xff, 0x00, 0x01, 0x02, 0x00, 0x00, 0x00]).is_ok());
+        // the maximum data length must not overflow the expected frame length
+        let frame = parse_mstp(&[0x55, 0xff, 0x06, 0x01, 0x02, 0xff, 0xff, 0x00]).unwrap();
+        assert_eq!(frame.data_len(), 0xffff);
+        assert!(frame.npdu().is_none());
+    }
+
+    #[test]
+    fn short_forwarded_npdu_test() {
+        // Forwarded-NPDU with the length claiming fewer bytes than the IP and port need
+        let bytes: &[u8] = &[0x81, 0x04, 0x00, 0x08, 0xc0, 0xa8, 0x01, 0x01];
+        assert!(parse_bvlc(bytes).is_err());
+    }
+
+    #[test]
+    fn short_enum_bytes_test() {
+        assert!(ObjectType::parse(&[]).is_err());
+        assert!(ObjectType::parse(&[0x00]).is_err());
+        assert_eq!(
+            ObjectType::parse(&[0x00, 0x08]).unwrap(),
+            ObjectType::ObjectDevice
+        );
+        assert!(PropertyId::parse(&[0x00]).is_err());
+        assert_eq!(
+            PropertyId::parse(&[0x00, 0x55]).unwrap(),
+            PropertyId::PropPresentValue
+        );
+    }
 }
@@ -3,41 +3,45 @@ use super::*;
 use arrayref::array_ref;
 use core::convert::From;
 
-pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs, Error> {
-    if bytes[0] != 0x55 || bytes[1] != 0xFF {
-        return Err(Error::InvalidValue("not the mstp preamble"));
-    }
+pub fn parse_mstp_skip_crc_compute(bytes: &[u8]) -> Result<MSTPFrameNoCrcs<'_>, Error> {
     if bytes.len() < 8 {
         return Err(Error::Length(
             "data is shorter than minimum mstp frame size",
         ));
     }
-    let mut frame = MSTPFrameNoCrcs::default();
-    frame.frame_type = bytes[2];
-    frame.dst_mac = bytes[3];
-    frame.src_mac = bytes[4];
-    frame.len = u16::from_be_bytes(*array_ref!(bytes, 5, 2));
+    if bytes[0] != 0x55 || bytes[1] != 0xFF {
+        return Err(Error::InvalidValue("not the mstp preamble"));
+    }
+    let mut frame = MSTPFrameNoCrcs {
+        frame_type: bytes[2],
+        dst_mac: bytes[3],
+        src_mac: bytes[4],
+        len: u16::from_be_bytes(*array_ref!(bytes, 5, 2)),
+        npdu: None,
+    };
     if frame.len == 0 {
         return Ok(frame);
     }
     // 10 comes from (header = 8) + (crc = 2)
-    if (10 + frame.len) as usize != bytes.len() {
+    if 10 + frame.len as usize != bytes.len() {
         // error but recoverable
         return Ok(frame);
     }
-    if let Ok(npdu) = parse_npdu(&bytes[8..]) {
+    if let Ok(npdu) = parse_npdu(&bytes[8..bytes.len() - 2]) {
         frame.npdu = Some(npdu);
     }
     Ok(frame)
 }
 
-pub fn parse_mstp(bytes: &[u8]) -> Result<MSTPFrame, Error> {
+pub fn parse_mstp(bytes: &[u8]) -> Result<MSTPFrame<'_>, Error> {
     let frame = parse_mstp_skip_crc_compute(bytes)?;
     let framelen = bytes.len();
 
-    let mut crcs = CRCs::default();
-    crcs.header_actual = bytes[7];
-    crcs.header_computed = compute_header_crc(*array_ref!(bytes, 2, 5));
+    let mut crcs = CRCs {
+        header_actual: bytes[7],
+        header_computed: compute_header_crc(&bytes[2..7]),
+        ..Default::default()
+    };
     if framelen > 10 {
         crcs.data_actual = u16::from_le_bytes(*array_ref!(bytes, framelen - 2, 2));
         crcs.data_computed = compute_data_crc(&bytes[8..framelen - 2]);
@@ -115,10 +119,10 @@ impl<'a> MSTPFrame<'a> {
 
 #[derive(Clone, Copy, Default)]
 pub struct CRCs {
-    header_computed: u8,
-    header_actual: u8,
-    data_computed: u16,
-    data_actual: u16,
+    pub(crate) header_computed: u8,
+    pub(crate) header_actual: u8,
+    pub(crate) data_computed: u16,
+    pub(crate) data_actual: u16,
 }
 
 impl CRCs {
@@ -146,6 +150,7 @@ pub enum MSTPFrameType {
     Proprietary,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for MSTPFrameType {
     fn default() -> Self {
         Self::Reserved
@@ -169,11 +174,63 @@ impl From<u8> for MSTPFrameType {
     }
 }
 
-/// The 5 input bytes are the frame type, destination, source, and 2xlength bytes.
-fn compute_header_crc(bytes: [u8; 5]) -> u8 {
+/// Write an MS/TP frame with the preamble, header, header CRC, data and data CRC into `buf`. The
+/// data CRC is only written if `payload` is not empty. Returns the number of bytes written.
+///
+/// Reserved and proprietary frame types cannot be encoded because they do not carry the frame
+/// type byte.
+pub fn encode_mstp(
+    buf: &mut [u8],
+    frame_type: MSTPFrameType,
+    dst_mac: u8,
+    src_mac: u8,
+    payload: &[u8],
+) -> Result<usize, Error> {
+    let frame_type = match frame_type {
+        MSTPFrameType::Token => 0,
+        MSTPFrameType::PollforMaster => 1,
+        MSTPFrameType::ReplyToPollForMaster => 2,
+        MSTPFrameType::TestRequest => 3,
+        MSTPFrameType::TestResponse => 4,
+        MSTPFrameType::BACnetDataExpectingReply => 5,
+        MSTPFrameType::BACnetDataNotExpectingReply => 6,
+        MSTPFrameType::ReplyPostponed => 7,
+        MSTPFrameType::Reserved | MSTPFrameType::Proprietary => {
+            return Err(Error::InvalidValue(
+                "cannot encode a reserved or proprietary mstp frame type",
+            ))
+        }
+    };
+    if payload.len() > usize::from(u16::MAX) {
+        return Err(Error::Length("mstp data length too large"));
+    }
+    let len = if payload.is_empty() {
+        8
+    } else {
+        10 + payload.len()
+    };
+    if buf.len() < len {
+        return Err(Error::Length("insufficient buffer size for mstp"));
+    }
+    buf[0] = 0x55;
+    buf[1] = 0xFF;
+    buf[2] = frame_type;
+    buf[3] = dst_mac;
+    buf[4] = src_mac;
+    buf[5..7].copy_from_slice(&(payload.len() as u16).to_be_bytes());
+    buf[7] = compute_header_crc(&buf[2..7]);
+    if !payload.is_empty() {
+        buf[8..len - 2].copy_from_slice(payload);
+        buf[len - 2..len].copy_from_slice(&compute_data_crc(payload).to_le_bytes());
+    }
+    Ok(len)
+}
+
+/// For MS/TP, the 5 input bytes are the frame type, destination, source, and 2xlength bytes.
+pub(crate) fn compute_header_crc(bytes: &[u8]) -> u8 {
     // algorithm translated from BACnet standard
     let mut crc: u16 = 0xFF;
-    for b in &bytes {
+    for b in bytes {
         crc ^= u16::from(*b);
         crc ^= (crc << 1)
             ^ (crc << 2)
@@ -188,7 +245,7 @@ fn compute_header_crc(bytes: [u8; 5]) -> u8 {
 }
 
 /// The data should be everything in between the header crc and data crc.
-fn compute_data_crc(bytes: &[u8]) -> u16 {
+pub(crate) fn compute_data_crc(bytes: &[u8]) -> u16 {
     // algorithm translated from BACnet standard
     let mut crc: u16 = 0xFFFF;
     for b in bytes {
@@ -211,7 +268,7 @@ mod tests {
     #[test]
     fn header_crc_test() {
         const HEADER: &[u8] = &[0x55, 0xff, 0x06, 0x7f, 0x02, 0x00, 0x1d, 0x90];
-        assert_eq!(compute_header_crc(*array_ref!(HEADER, 2, 5)), 0x90);
+        assert_eq!(compute_header_crc(&HEADER[2..7]), 0x90);
     }
 
     #[test]
@@ -238,6 +295,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn parse_no_crc() {
         const DATA: &[u8] = &[
             0x55, 0xff, 0x05, 0x0c, 0x7f, 0x00, 0x1f, 0x35, 0x01, 0x0c, 0x00, 0x01, 0x06, 0xc0,
@@ -280,6 +338,74 @@ mod tests {
         assert_eq!(actual, 0x6fc9);
     }
 
+    #[test]
+    fn encode_test() {
+        const DATA: &[u8] = &[
+            0x55, 0xff, 0x05, 0x0c, 0x7f, 0x00, 0x1f, 0x35, 0x01, 0x0c, 0x00, 0x01, 0x06, 0xc0,
+            0xa8, 0x01, 0x12, 0xba, 0xc0, 0x02, 0x01, 0x6a, 0x0f, 0x0c, 0x00, 0x80, 0x00, 0x0a,
+            0x19, 0x55, 0x3e, 0x44, 0x41, 0xe8, 0x00, 0x01, 0x3f, 0x49, 0x09, 0xc9, 0x6f,
+        ];
+        let mut buf = [0u8; 64];
+        let len = encode_mstp(
+            &mut buf,
+            MSTPFrameType::BACnetDataExpectingReply,
+            0x0c,
+            0x7f,
+            &DATA[8..39],
+        )
+        .unwrap();
+        assert_eq!(&buf[..len], DATA);
+        let frame = parse_mstp(&buf[..len]).unwrap();
+        assert_eq!(frame.frame_type(), MSTPFrameType::BACnetDataExpectingReply);
+        assert_eq!(frame.dst_mac(), 0x0c);
+        assert_eq!(frame.src_mac(), 0x7f);
+        let (actual, computed) = frame.crcs().header();
+        assert_eq!(actual, computed);
+        let (actual, computed) = frame.crcs().data();
+        assert_eq!(actual, computed);
+        assert_eq!(frame.npdu().as_ref().unwrap().payload(), &DATA[19..39]);
+    }
+
+    #[test]
+    fn encode_no_data_test() {
+        let mut buf = [0u8; 8];
+        let len = encode_mstp(&mut buf, MSTPFrameType::Token, 0x06, 0x7f, &[]).unwrap();
+        assert_eq!(len, 8);
+        let frame = parse_mstp(&buf).unwrap();
+        assert_eq!(frame.frame_type(), MSTPFrameType::Token);
+        assert_eq!(frame.dst_mac(), 0x06);
+        assert_eq!(frame.src_mac(), 0x7f);
+        assert_eq!(frame.data_len(), 0);
+        let (actual, computed) = frame.crcs().header();
+        assert_eq!(actual, computed);
+
+        assert!(encode_mstp(&mut buf[..7], MSTPFrameType::Token, 0x06, 0x7f, &[]).is_err());
+        assert!(encode_mstp(&mut buf, MSTPFrameType::TestRequest, 0x06, 0x7f, &[0x01]).is_err());
+        assert!(encode_mstp(&mut buf, MSTPFrameType::Reserved, 0x06, 0x7f, &[]).is_err());
+        assert!(encode_mstp(&mut buf, MSTPFrameType::Proprietary, 0x06, 0x7f, &[]).is_err());
+
+        for b in 0..8 {
+            let frame_type = MSTPFrameType::from(b);
+            encode_mstp(&mut buf, frame_type, 0x06, 0x7f, &[]).unwrap();
+            assert_eq!(buf[2], b);
+            assert_eq!(parse_mstp(&buf).unwrap().frame_type(), frame_type);
+        }
+    }
+
+    #[test]
+    fn npdu_excludes_data_crc() {
+        const DATA: &[u8] = &[
+            0x55, 0xff, 0x06, 0x0c, 0x7f, 0x00, 0x05, 0x00, 0x01, 0x00, 0x10, 0x08, 0x00, 0xaa,
+            0xbb,
+        ];
+        let frame = parse_mstp_skip_crc_compute(DATA).unwrap();
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert_eq!(npdu.payload(), &[0x10, 0x08, 0x00]);
+        let frame = parse_mstp(DATA).unwrap();
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert_eq!(npdu.payload(), &[0x10, 0x08, 0x00]);
+    }
+
     #[test]
     fn parse_crc_unequal() {
         const DATA: &[u8] = &[
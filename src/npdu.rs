@@ -1,7 +1,7 @@
 use crate::Error;
 use arrayref::array_ref;
 
-pub fn parse_npdu(bytes: &[u8]) -> Result<NPDU, Error> {
+pub fn parse_npdu(bytes: &[u8]) -> Result<NPDU<'_>, Error> {
     if bytes.len() < 3 {
         return Err(Error::Length("insufficient size for npdu"));
     }
@@ -78,10 +78,8 @@ impl<'a> NPDU<'a> {
             0 => NCPIPriority::Normal,
             1 => NCPIPriority::Urgent,
             2 => NCPIPriority::CriticalEquip,
-            3 => NCPIPriority::LifeSafety,
-            // Safety:
-            // The byte is bitwise ANDed with 3, i.e. 0b11, which has a max value of 3.
-            _ => unsafe { core::hint::unreachable_unchecked() },
+            // The byte is bitwise ANDed with 3, i.e. 0b11, so the only remaining value is 3.
+            _ => NCPIPriority::LifeSafety,
         }
     }
 
@@ -168,4 +166,16 @@ mod tests {
         assert_eq!(netaddr.net(), 0x1234);
         assert_eq!(netaddr.addr(), &[192, 168, 1, 10]);
     }
+
+    #[test]
+    fn prio_test() {
+        let prio = |control| parse_npdu(&[0x01, control, 0x10]).unwrap().prio();
+        assert_eq!(prio(0x00), NCPIPriority::Normal);
+        assert_eq!(prio(0x01), NCPIPriority::Urgent);
+        assert_eq!(prio(0x02), NCPIPriority::CriticalEquip);
+        assert_eq!(prio(0x03), NCPIPriority::LifeSafety);
+        // only the lowest 2 bits are the priority
+        assert_eq!(prio(0x04), NCPIPriority::Normal);
+        assert_eq!(prio(0x07), NCPIPriority::LifeSafety);
+    }
 }
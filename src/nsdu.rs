@@ -4,28 +4,53 @@ pub mod property_id;
 pub mod rpdu;
 use crate::Error;
 pub use apdu::parse_apdu;
-use arrayref::array_ref;
+use core::convert::TryFrom;
 pub use rpdu::parse_rpdu;
 
 fn parse_unsigned(bytes: &[u8], sz: u32) -> Result<(&[u8], u32), Error> {
-    let sz = sz as usize;
     if sz > 4 || sz == 0 {
         return Err(Error::InvalidValue(
             "unsigned len value is 0 or greater than 4",
         ));
     }
+    let (bytes, val) = parse_unsigned64(bytes, sz)?;
+    // at most 4 bytes were read so the value fits in a u32
+    Ok((bytes, val as u32))
+}
+
+fn parse_unsigned64(bytes: &[u8], sz: u32) -> Result<(&[u8], u64), Error> {
+    let sz = sz as usize;
+    if sz > 8 || sz == 0 {
+        return Err(Error::InvalidValue(
+            "unsigned len value is 0 or greater than 8",
+        ));
+    }
     if bytes.len() < sz {
         return Err(Error::Length(
             "unsigned len value greater than remaining bytes",
         ));
     }
-    let val = match sz {
-        1 => bytes[0] as u32,
-        2 => u16::from_be_bytes(*array_ref!(bytes, 0, 2)) as u32,
-        3 => ((bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32),
-        4 => u32::from_be_bytes(*array_ref!(bytes, 0, 4)),
-        // Safety: this value is checked at the beginning of the fn.
-        _ => unsafe { core::hint::unreachable_unchecked() },
-    };
+    let val = bytes[..sz]
+        .iter()
+        .fold(0u64, |val, b| (val << 8) | u64::from(*b));
     Ok((&bytes[sz..], val))
 }
+
+fn parse_signed(bytes: &[u8], sz: u32) -> Result<(&[u8], i64), Error> {
+    let (bytes, val) = parse_unsigned64(bytes, sz)?;
+    // shift the sign bit of the value into the sign bit of the i64 then shift back to extend it
+    let shift = 64 - 8 * sz;
+    Ok((bytes, ((val << shift) as i64) >> shift))
+}
+
+fn parse_unsigned16(bytes: &[u8], sz: u32) -> Result<(&[u8], u16), Error> {
+    let (bytes, val) = parse_unsigned(bytes, sz)?;
+    let val = u16::try_from(val).map_err(|_| Error::InvalidValue("unsigned larger than u16"))?;
+    Ok((bytes, val))
+}
+
+fn parse_unsigned8(bytes: &[u8], sz: u32) -> Result<(&[u8], u8), Error> {
+    let (bytes, val) = parse_unsigned(bytes, sz)?;
+    let val = u8::try_from(val).map_err(|_| Error::InvalidValue("unsigned larger than u8"))?;
+    Ok((bytes, val))
+}
@@ -1,8 +1,20 @@
+use crate::nsdu::{parse_unsigned, parse_unsigned16};
 use crate::Error;
-mod unconfirmed_request_pdu;
-mod tag;
+use error::{BACnetError, ErrorClass, ErrorCode};
+use tag::{parse_context_tagged, Tag};
+use value::ObjectPropertyReference;
+pub mod confirmed_request_pdu;
+pub mod cov;
+pub mod error;
+pub mod event;
+pub mod read_property;
+pub mod read_property_multiple;
+pub mod tag;
+pub mod unconfirmed_request_pdu;
+pub mod value;
+pub mod write_property;
 
-pub fn parse_apdu(bytes: &[u8]) -> Result<APDU, Error> {
+pub fn parse_apdu(bytes: &[u8]) -> Result<APDU<'_>, Error> {
     if bytes.is_empty() {
         return Err(Error::Length("empty apdu bytes"));
     }
@@ -24,9 +36,24 @@ impl<'a> APDU<'a> {
     pub fn pdu_type_byte(&self) -> u8 {
         self.pdu_type
     }
+    /// Returns the confirmed service choice of Confirmed-Request, Simple-ACK, Complex-ACK and
+    /// Error PDUs, or `None` for other PDU types or if the APDU is too short.
+    pub fn confirmed_service_choice(&self) -> Option<ConfirmedServiceChoice> {
+        // segmented messages carry a sequence number and window size before the service choice
+        let segmented = self.pdu_type & 0x08 != 0;
+        let idx = match self.pdu_type() {
+            PDUType::BACnetConfirmedRequestPDU if segmented => 5,
+            PDUType::BACnetConfirmedRequestPDU => 3,
+            PDUType::BACnetComplexACKPDU if segmented => 4,
+            PDUType::BACnetSimpleACKPDU | PDUType::BACnetComplexACKPDU | PDUType::Error => 2,
+            _ => return None,
+        };
+        self.bytes.get(idx).map(|&b| b.into())
+    }
 }
 
 /// Classification of APDU service. There are multiple services within each PDU type.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PDUType {
     BACnetConfirmedRequestPDU,
     BACnetUnconfirmedRequestPDU,
@@ -41,49 +68,157 @@ pub enum PDUType {
 
 impl From<u8> for PDUType {
     fn from(b: u8) -> Self {
-        match b & 0xF0 {
-            0x00 => Self::BACnetConfirmedRequestPDU,
-            0x10 => Self::BACnetUnconfirmedRequestPDU,
-            0x20 => Self::BACnetSimpleACKPDU,
-            0x30 => Self::BACnetComplexACKPDU,
-            0x40 => Self::Segment,
-            0x50 => Self::Error,
-            0x60 => Self::RejectPDU,
-            0x70 => Self::Abort,
-            0x80..=0xF0 => Self::Reserved,
-            // Safety: the byte is bitwise ANDed  with 0xF0, thus anything without a zero in the
-            // lower nibble need not be checked.
-            _ => unsafe { core::hint::unreachable_unchecked() },
+        // the PDU type is the upper nibble
+        match b >> 4 {
+            0x0 => Self::BACnetConfirmedRequestPDU,
+            0x1 => Self::BACnetUnconfirmedRequestPDU,
+            0x2 => Self::BACnetSimpleACKPDU,
+            0x3 => Self::BACnetComplexACKPDU,
+            0x4 => Self::Segment,
+            0x5 => Self::Error,
+            0x6 => Self::RejectPDU,
+            0x7 => Self::Abort,
+            _ => Self::Reserved,
         }
     }
 }
 
+/// The service choice of confirmed services, used in Confirmed-Request, Simple-ACK, Complex-ACK
+/// and Error PDUs. Services which have been removed from the standard are included for decoding
+/// older devices.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ConfirmedServiceChoice {
+    // Alarm and event services
+    AcknowledgeAlarm,
+    ConfirmedCOVNotification,
+    ConfirmedEventNotification,
+    GetAlarmSummary,
+    GetEnrollmentSummary,
     SubscribeCOV,
+    // File access services
+    AtomicReadFile,
+    AtomicWriteFile,
+    // Object access services
+    AddListElement,
+    RemoveListElement,
+    CreateObject,
+    DeleteObject,
     ReadProperty,
+    ReadPropertyConditional,
     ReadPropertyMultiple,
     WriteProperty,
     WritePropertyMultiple,
+    // Remote device management services
     DeviceCommunicationControl,
+    ConfirmedPrivateTransfer,
+    ConfirmedTextMessage,
     ReinitializeDevice,
-    Unknown,
+    // Virtual terminal services
+    VTOpen,
+    VTClose,
+    VTData,
+    // Security services
+    Authenticate,
+    RequestKey,
+    // Object access services
+    ReadRange,
+    // Alarm and event services
+    LifeSafetyOperation,
+    SubscribeCOVProperty,
+    GetEventInformation,
+    SubscribeCOVPropertyMultiple,
+    ConfirmedCOVNotificationMultiple,
+    // Audit reporting services
+    ConfirmedAuditNotification,
+    AuditLogQuery,
+    Unknown(u8),
 }
 
 impl From<u8> for ConfirmedServiceChoice {
     fn from(b: u8) -> Self {
         match b {
+            0x00 => Self::AcknowledgeAlarm,
+            0x01 => Self::ConfirmedCOVNotification,
+            0x02 => Self::ConfirmedEventNotification,
+            0x03 => Self::GetAlarmSummary,
+            0x04 => Self::GetEnrollmentSummary,
             0x05 => Self::SubscribeCOV,
+            0x06 => Self::AtomicReadFile,
+            0x07 => Self::AtomicWriteFile,
+            0x08 => Self::AddListElement,
+            0x09 => Self::RemoveListElement,
+            0x0a => Self::CreateObject,
+            0x0b => Self::DeleteObject,
             0x0c => Self::ReadProperty,
+            0x0d => Self::ReadPropertyConditional,
             0x0e => Self::ReadPropertyMultiple,
             0x0f => Self::WriteProperty,
             0x10 => Self::WritePropertyMultiple,
             0x11 => Self::DeviceCommunicationControl,
+            0x12 => Self::ConfirmedPrivateTransfer,
+            0x13 => Self::ConfirmedTextMessage,
             0x14 => Self::ReinitializeDevice,
-            _ => Self::Unknown,
+            0x15 => Self::VTOpen,
+            0x16 => Self::VTClose,
+            0x17 => Self::VTData,
+            0x18 => Self::Authenticate,
+            0x19 => Self::RequestKey,
+            0x1a => Self::ReadRange,
+            0x1b => Self::LifeSafetyOperation,
+            0x1c => Self::SubscribeCOVProperty,
+            0x1d => Self::GetEventInformation,
+            0x1e => Self::SubscribeCOVPropertyMultiple,
+            0x1f => Self::ConfirmedCOVNotificationMultiple,
+            0x20 => Self::ConfirmedAuditNotification,
+            0x21 => Self::AuditLogQuery,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+impl From<ConfirmedServiceChoice> for u8 {
+    fn from(c: ConfirmedServiceChoice) -> Self {
+        match c {
+            ConfirmedServiceChoice::AcknowledgeAlarm => 0x00,
+            ConfirmedServiceChoice::ConfirmedCOVNotification => 0x01,
+            ConfirmedServiceChoice::ConfirmedEventNotification => 0x02,
+            ConfirmedServiceChoice::GetAlarmSummary => 0x03,
+            ConfirmedServiceChoice::GetEnrollmentSummary => 0x04,
+            ConfirmedServiceChoice::SubscribeCOV => 0x05,
+            ConfirmedServiceChoice::AtomicReadFile => 0x06,
+            ConfirmedServiceChoice::AtomicWriteFile => 0x07,
+            ConfirmedServiceChoice::AddListElement => 0x08,
+            ConfirmedServiceChoice::RemoveListElement => 0x09,
+            ConfirmedServiceChoice::CreateObject => 0x0a,
+            ConfirmedServiceChoice::DeleteObject => 0x0b,
+            ConfirmedServiceChoice::ReadProperty => 0x0c,
+            ConfirmedServiceChoice::ReadPropertyConditional => 0x0d,
+            ConfirmedServiceChoice::ReadPropertyMultiple => 0x0e,
+            ConfirmedServiceChoice::WriteProperty => 0x0f,
+            ConfirmedServiceChoice::WritePropertyMultiple => 0x10,
+            ConfirmedServiceChoice::DeviceCommunicationControl => 0x11,
+            ConfirmedServiceChoice::ConfirmedPrivateTransfer => 0x12,
+            ConfirmedServiceChoice::ConfirmedTextMessage => 0x13,
+            ConfirmedServiceChoice::ReinitializeDevice => 0x14,
+            ConfirmedServiceChoice::VTOpen => 0x15,
+            ConfirmedServiceChoice::VTClose => 0x16,
+            ConfirmedServiceChoice::VTData => 0x17,
+            ConfirmedServiceChoice::Authenticate => 0x18,
+            ConfirmedServiceChoice::RequestKey => 0x19,
+            ConfirmedServiceChoice::ReadRange => 0x1a,
+            ConfirmedServiceChoice::LifeSafetyOperation => 0x1b,
+            ConfirmedServiceChoice::SubscribeCOVProperty => 0x1c,
+            ConfirmedServiceChoice::GetEventInformation => 0x1d,
+            ConfirmedServiceChoice::SubscribeCOVPropertyMultiple => 0x1e,
+            ConfirmedServiceChoice::ConfirmedCOVNotificationMultiple => 0x1f,
+            ConfirmedServiceChoice::ConfirmedAuditNotification => 0x20,
+            ConfirmedServiceChoice::AuditLogQuery => 0x21,
+            ConfirmedServiceChoice::Unknown(b) => b,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum BACnetRejectReason {
     Other,
     BufferOverflow,
@@ -116,37 +251,405 @@ impl From<u8> for BACnetRejectReason {
     }
 }
 
-pub struct ErrorPDU {
+/// Segmentation support advertised by a device, e.g. in an I-Am.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Segmentation {
+    SegmentedBoth,
+    SegmentedTransmit,
+    SegmentedReceive,
+    NoSegmentation,
+    Unknown,
+}
+
+impl From<u32> for Segmentation {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::SegmentedBoth,
+            1 => Self::SegmentedTransmit,
+            2 => Self::SegmentedReceive,
+            3 => Self::NoSegmentation,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AbortReason {
+    Other,
+    BufferOverflow,
+    InvalidAPDUInThisState,
+    PreemptedByHigherPriorityTask,
+    SegmentationNotSupported,
+    SecurityError,
+    InsufficientSecurity,
+    WindowSizeOutOfRange,
+    ApplicationExceededReplyTime,
+    OutOfResources,
+    TSMTimeout,
+    APDUTooLong,
+    Reserved,
+    Proprietary,
+}
+
+impl From<u8> for AbortReason {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Other,
+            1 => Self::BufferOverflow,
+            2 => Self::InvalidAPDUInThisState,
+            3 => Self::PreemptedByHigherPriorityTask,
+            4 => Self::SegmentationNotSupported,
+            5 => Self::SecurityError,
+            6 => Self::InsufficientSecurity,
+            7 => Self::WindowSizeOutOfRange,
+            8 => Self::ApplicationExceededReplyTime,
+            9 => Self::OutOfResources,
+            10 => Self::TSMTimeout,
+            11 => Self::APDUTooLong,
+            12..=63 => Self::Reserved,
+            64..=255 => Self::Proprietary,
+        }
+    }
+}
+
+pub struct SimpleACKPDU {
+    invoke_id: u8,
+    service_choice: u8,
+}
+
+impl SimpleACKPDU {
+    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::BACnetSimpleACKPDU) {
+            return Err(Error::InvalidValue("not a simple ack pdu"));
+        }
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for SimpleACKPDU"));
+        }
+        Ok(Self {
+            invoke_id: b[1],
+            service_choice: b[2],
+        })
+    }
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+}
+
+pub struct ComplexACKPDU<'a> {
+    control: u8,
+    invoke_id: u8,
+    sequence_number: Option<u8>,
+    proposed_window_size: Option<u8>,
+    service_choice: u8,
+    service_ack: &'a [u8],
+}
+
+impl<'a> ComplexACKPDU<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::BACnetComplexACKPDU) {
+            return Err(Error::InvalidValue("not a complex ack pdu"));
+        }
+        // The sequence number and proposed window size are only present in segmented messages.
+        let segmented = b[0] & 0x08 != 0;
+        let service_choice_idx = if segmented { 4 } else { 2 };
+        if b.len() <= service_choice_idx {
+            return Err(Error::Length("insufficient size for ComplexACKPDU"));
+        }
+        Ok(Self {
+            control: b[0],
+            invoke_id: b[1],
+            sequence_number: if segmented { Some(b[2]) } else { None },
+            proposed_window_size: if segmented { Some(b[3]) } else { None },
+            service_choice: b[service_choice_idx],
+            service_ack: &b[service_choice_idx + 1..],
+        })
+    }
+    pub fn is_segmented_message(&self) -> bool {
+        self.control & 0x08 != 0
+    }
+    pub fn is_more_follows(&self) -> bool {
+        self.control & 0x04 != 0
+    }
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+    /// Only present if this is a segmented message.
+    pub fn sequence_number(&self) -> Option<u8> {
+        self.sequence_number
+    }
+    /// Only present if this is a segmented message.
+    pub fn proposed_window_size(&self) -> Option<u8> {
+        self.proposed_window_size
+    }
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+    /// The service ACK bytes after the header.
+    pub fn service_ack(&self) -> &'a [u8] {
+        self.service_ack
+    }
+}
+
+pub struct SegmentACKPDU {
+    control: u8,
+    invoke_id: u8,
+    sequence_number: u8,
+    actual_window_size: u8,
+}
+
+impl SegmentACKPDU {
+    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::Segment) {
+            return Err(Error::InvalidValue("not a segment ack pdu"));
+        }
+        if b.len() < 4 {
+            return Err(Error::Length("insufficient size for SegmentACKPDU"));
+        }
+        Ok(Self {
+            control: b[0],
+            invoke_id: b[1],
+            sequence_number: b[2],
+            actual_window_size: b[3],
+        })
+    }
+    /// `true` if this is a negative acknowledgement of a segment received out of order.
+    pub fn is_nak(&self) -> bool {
+        self.control & 0x02 != 0
+    }
+    /// `true` if sent by a server, `false` if sent by a client.
+    pub fn is_server(&self) -> bool {
+        self.control & 0x01 != 0
+    }
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+    pub fn sequence_number(&self) -> u8 {
+        self.sequence_number
+    }
+    pub fn actual_window_size(&self) -> u8 {
+        self.actual_window_size
+    }
+}
+
+pub struct RejectPDU {
+    invoke_id: u8,
+    reason: u8,
+}
+
+impl RejectPDU {
+    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::RejectPDU) {
+            return Err(Error::InvalidValue("not a reject pdu"));
+        }
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for RejectPDU"));
+        }
+        Ok(Self {
+            invoke_id: b[1],
+            reason: b[2],
+        })
+    }
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+    pub fn reason(&self) -> BACnetRejectReason {
+        self.reason.into()
+    }
+    pub fn reason_byte(&self) -> u8 {
+        self.reason
+    }
+}
+
+pub struct AbortPDU {
+    control: u8,
     invoke_id: u8,
-    error_class: u8,
-    error_code: u8,
+    reason: u8,
 }
 
-impl ErrorPDU {
-    fn parse(b: &[u8]) -> Result<Self, Error> {
-        if b.len() != 3 {
-            return Err(Error::Length("wrong len for ErrorPDU"));
+impl AbortPDU {
+    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::Abort) {
+            return Err(Error::InvalidValue("not an abort pdu"));
+        }
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for AbortPDU"));
         }
         Ok(Self {
-            invoke_id: b[0],
-            error_class: b[1],
-            error_code: b[2],
+            control: b[0],
+            invoke_id: b[1],
+            reason: b[2],
         })
     }
+    /// `true` if sent by a server, `false` if sent by a client.
+    pub fn is_server(&self) -> bool {
+        self.control & 0x01 != 0
+    }
     pub fn invoke_id(&self) -> u8 {
         self.invoke_id
     }
-    pub fn error_class(&self) -> u8 {
-        self.error_class
+    pub fn reason(&self) -> AbortReason {
+        self.reason.into()
     }
-    pub fn error_code(&self) -> u8 {
-        self.error_code
+    pub fn reason_byte(&self) -> u8 {
+        self.reason
     }
 }
 
+pub struct ErrorPDU<'a> {
+    invoke_id: u8,
+    service_choice: u8,
+    error: BACnetError,
+    details: ErrorDetails<'a>,
+}
+
+impl<'a> ErrorPDU<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
+        let b = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::Error) {
+            return Err(Error::InvalidValue("not an error pdu"));
+        }
+        if b.len() < 3 {
+            return Err(Error::Length("insufficient size for ErrorPDU"));
+        }
+        let (error, details) = match ConfirmedServiceChoice::from(b[2]) {
+            ConfirmedServiceChoice::AddListElement | ConfirmedServiceChoice::RemoveListElement => {
+                let (bytes, error) = parse_error_type(&b[3..])?;
+                let (_, first_failed_element_number) =
+                    parse_context_tagged(bytes, 1, parse_unsigned)?;
+                (
+                    error,
+                    ErrorDetails::ChangeList {
+                        first_failed_element_number,
+                    },
+                )
+            }
+            ConfirmedServiceChoice::CreateObject => {
+                let (bytes, error) = parse_error_type(&b[3..])?;
+                let (_, first_failed_element_number) =
+                    parse_context_tagged(bytes, 1, parse_unsigned)?;
+                (
+                    error,
+                    ErrorDetails::CreateObject {
+                        first_failed_element_number,
+                    },
+                )
+            }
+            ConfirmedServiceChoice::WritePropertyMultiple => {
+                let (bytes, error) = parse_error_type(&b[3..])?;
+                let (_, attempt) = Tag::parse_constructed(bytes, 1)?;
+                let (_, first_failed_write_attempt) = ObjectPropertyReference::parse(attempt)?;
+                (
+                    error,
+                    ErrorDetails::WritePropertyMultiple {
+                        first_failed_write_attempt,
+                    },
+                )
+            }
+            ConfirmedServiceChoice::ConfirmedPrivateTransfer => {
+                let (bytes, error) = parse_error_type(&b[3..])?;
+                let (bytes, vendor_id) = parse_context_tagged(bytes, 1, parse_unsigned16)?;
+                let (bytes, service_number) = parse_context_tagged(bytes, 2, parse_unsigned)?;
+                let error_parameters = if Tag::is_opening(bytes, 3) {
+                    Some(Tag::parse_constructed(bytes, 3)?.1)
+                } else {
+                    None
+                };
+                (
+                    error,
+                    ErrorDetails::ConfirmedPrivateTransfer {
+                        vendor_id,
+                        service_number,
+                        error_parameters,
+                    },
+                )
+            }
+            // Other services with structured errors, e.g. VTClose-Error
+            _ if Tag::is_opening(&b[3..], 0) => {
+                let (bytes, error) = parse_error_type(&b[3..])?;
+                (error, ErrorDetails::Other(bytes))
+            }
+            _ => (BACnetError::parse(&b[3..])?.1, ErrorDetails::None),
+        };
+        Ok(Self {
+            invoke_id: b[1],
+            service_choice: b[2],
+            error,
+            details,
+        })
+    }
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+    pub fn error_class(&self) -> ErrorClass {
+        self.error.error_class
+    }
+    pub fn error_code(&self) -> ErrorCode {
+        self.error.error_code
+    }
+    /// Additional error parameters for services that return more than an error class and code.
+    pub fn details(&self) -> &ErrorDetails<'a> {
+        &self.details
+    }
+}
+
+/// Parse the `[0] errorType` that begins a service specific error.
+fn parse_error_type(bytes: &[u8]) -> Result<(&[u8], BACnetError), Error> {
+    let (rest, error) = Tag::parse_constructed(bytes, 0)?;
+    Ok((rest, BACnetError::parse(error)?.1))
+}
+
+/// Parameters in addition to the error class and code returned by some services.
+#[derive(Debug, PartialEq)]
+pub enum ErrorDetails<'a> {
+    None,
+    ChangeList {
+        first_failed_element_number: u32,
+    },
+    CreateObject {
+        first_failed_element_number: u32,
+    },
+    WritePropertyMultiple {
+        first_failed_write_attempt: ObjectPropertyReference,
+    },
+    ConfirmedPrivateTransfer {
+        vendor_id: u16,
+        service_number: u32,
+        /// The encoded error parameters, excluding the enclosing tags.
+        error_parameters: Option<&'a [u8]>,
+    },
+    /// The encoded parameters after the error type of other service specific errors.
+    Other(&'a [u8]),
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{unconfirmed_request_pdu::*, tag::*};
+    use super::error::{ErrorClass, ErrorCode};
+    use super::event::{EventState, EventType, NotifyType};
+    use super::unconfirmed_request_pdu::*;
+    use super::value::{ApplicationValue, CharacterSet, TimeStamp};
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+    use crate::nsdu::property_id::PropertyId;
     use crate::*;
 
     #[test]
@@ -155,7 +658,7 @@ mod tests {
             0x81, 0x0b, 0x00, 0x1b, 0x01, 0x28, 0xff, 0xff, 0x00, 0x27, 0x2f, 0x06, 0x00, 0x40,
             0xae, 0x04, 0xd3, 0xff, 0xfe, 0x10, 0x08, 0x0a, 0x0b, 0x54, 0x1a, 0x0b, 0x54,
         ];
-        let bvlc = parse_bvlc(&bytes).unwrap();
+        let bvlc = parse_bvlc(bytes).unwrap();
         let npdu = bvlc.npdu().as_ref().unwrap();
         let apdu = parse_apdu(npdu.payload()).unwrap();
         let ucs = UnconfirmedServiceChoice::parse(&apdu).unwrap();
@@ -168,4 +671,627 @@ mod tests {
             _ => panic!("should be WhoIs"),
         }
     }
+
+    #[test]
+    fn whois_limits_test() {
+        let whois = |bytes| match UnconfirmedServiceChoice::parse(&parse_apdu(bytes).unwrap()) {
+            Ok(UnconfirmedServiceChoice::WhoIs(lims)) => Ok(lims),
+            Ok(_) => panic!("should be WhoIs"),
+            Err(e) => Err(e),
+        };
+        assert!(whois(&[0x10]).is_err());
+        assert!(whois(&[0x10, 0x08]).unwrap().is_none());
+
+        let lims = whois(&[
+            0x10, 0x08, 0x0b, 0x01, 0x00, 0x00, 0x1c, 0x3f, 0xff, 0xff, 0xff,
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(lims.low_limit, 0x10000);
+        assert_eq!(lims.high_limit, 0x3fffffff);
+
+        // only the low limit
+        assert!(whois(&[0x10, 0x08, 0x09, 0x01]).is_err());
+        // application tagged limits
+        assert!(whois(&[0x10, 0x08, 0x21, 0x01, 0x21, 0x02]).is_err());
+        // limits out of order
+        assert!(whois(&[0x10, 0x08, 0x19, 0x02, 0x09, 0x01]).is_err());
+        // limit longer than 4 bytes
+        assert!(
+            whois(&[0x10, 0x08, 0x0d, 0x05, 0x00, 0x00, 0x00, 0x00, 0x01, 0x19, 0x01]).is_err()
+        );
+    }
+
+    #[test]
+    fn pdu_type_test() {
+        for b in 0..=255u8 {
+            let expected = match b {
+                0x00..=0x0f => PDUType::BACnetConfirmedRequestPDU,
+                0x10..=0x1f => PDUType::BACnetUnconfirmedRequestPDU,
+                0x20..=0x2f => PDUType::BACnetSimpleACKPDU,
+                0x30..=0x3f => PDUType::BACnetComplexACKPDU,
+                0x40..=0x4f => PDUType::Segment,
+                0x50..=0x5f => PDUType::Error,
+                0x60..=0x6f => PDUType::RejectPDU,
+                0x70..=0x7f => PDUType::Abort,
+                _ => PDUType::Reserved,
+            };
+            assert_eq!(PDUType::from(b), expected);
+            assert_eq!(parse_apdu(&[b]).unwrap().pdu_type(), expected);
+        }
+    }
+
+    #[test]
+    fn basic_iam_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x0b, 0x00, 0x19, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, 0x10, 0x00, 0xc4, 0x02,
+            0x00, 0x0b, 0x54, 0x22, 0x05, 0xc4, 0x91, 0x00, 0x22, 0x01, 0x04,
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        let apdu = parse_apdu(npdu.payload()).unwrap();
+        let ucs = UnconfirmedServiceChoice::parse(&apdu).unwrap();
+        match ucs {
+            UnconfirmedServiceChoice::IAm(iam) => {
+                assert_eq!(iam.device_id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(iam.device_id.instance, 2900);
+                assert_eq!(iam.max_apdu, 1476);
+                assert_eq!(iam.segmentation, Segmentation::SegmentedBoth);
+                assert_eq!(iam.vendor_id, 260);
+            }
+            _ => panic!("should be IAm"),
+        }
+    }
+
+    #[test]
+    fn iam_no_segmentation_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x0b, 0x00, 0x13, 0x01, 0x00, 0x10, 0x00, 0xc4, 0x02, 0x00, 0x00, 0x01, 0x21,
+            0x32, 0x91, 0x03, 0x21, 0x08,
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        let apdu = parse_apdu(npdu.payload()).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::IAm(iam) => {
+                assert_eq!(iam.device_id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(iam.device_id.instance, 1);
+                assert_eq!(iam.max_apdu, 50);
+                assert_eq!(iam.segmentation, Segmentation::NoSegmentation);
+                assert_eq!(iam.vendor_id, 8);
+            }
+            _ => panic!("should be IAm"),
+        }
+    }
+
+    #[test]
+    fn truncated_iam_test() {
+        let bytes: &[u8] = &[0x10, 0x00, 0xc4, 0x02, 0x00, 0x0b, 0x54, 0x22, 0x05];
+        let apdu = parse_apdu(bytes).unwrap();
+        assert!(UnconfirmedServiceChoice::parse(&apdu).is_err());
+    }
+
+    #[test]
+    fn whohas_name_test() {
+        let bytes: &[u8] = &[0x10, 0x07, 0x3d, 0x06, 0x00, 0x41, 0x48, 0x55, 0x2d, 0x31];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoHas(whohas) => {
+                assert!(whohas.limits.is_none());
+                match whohas.object {
+                    WhoHasObject::Name(name) => {
+                        assert_eq!(name.charset(), CharacterSet::Utf8);
+                        assert_eq!(name.as_str(), Some("AHU-1"));
+                    }
+                    _ => panic!("should be Name"),
+                }
+            }
+            _ => panic!("should be WhoHas"),
+        }
+    }
+
+    #[test]
+    fn whohas_id_limits_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x07, 0x09, 0x64, 0x1a, 0x01, 0x2c, 0x2c, 0x00, 0x00, 0x00, 0x05,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoHas(whohas) => {
+                let limits = whohas.limits.unwrap();
+                assert_eq!(limits.low_limit, 100);
+                assert_eq!(limits.high_limit, 300);
+                match whohas.object {
+                    WhoHasObject::Id(id) => {
+                        assert_eq!(id.object_type, ObjectType::ObjectAnalogInput);
+                        assert_eq!(id.instance, 5);
+                    }
+                    _ => panic!("should be Id"),
+                }
+            }
+            _ => panic!("should be WhoHas"),
+        }
+    }
+
+    #[test]
+    fn whohas_long_name_test() {
+        let mut bytes = [b'a'; 307];
+        bytes[..7].copy_from_slice(&[0x10, 0x07, 0x3d, 0xfe, 0x01, 0x2d, 0x00]);
+        let apdu = parse_apdu(&bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoHas(whohas) => match whohas.object {
+                WhoHasObject::Name(name) => assert_eq!(name.bytes().len(), 300),
+                _ => panic!("should be Name"),
+            },
+            _ => panic!("should be WhoHas"),
+        }
+    }
+
+    #[test]
+    fn ihave_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x0b, 0x00, 0x1a, 0x01, 0x00, 0x10, 0x01, 0xc4, 0x02, 0x00, 0x0b, 0x54, 0xc4,
+            0x00, 0x00, 0x00, 0x05, 0x75, 0x06, 0x00, 0x41, 0x48, 0x55, 0x2d, 0x31,
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        let apdu = parse_apdu(npdu.payload()).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::IHave(ihave) => {
+                assert_eq!(ihave.device_id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(ihave.device_id.instance, 2900);
+                assert_eq!(ihave.object_id.object_type, ObjectType::ObjectAnalogInput);
+                assert_eq!(ihave.object_id.instance, 5);
+                assert_eq!(ihave.object_name.as_str(), Some("AHU-1"));
+            }
+            _ => panic!("should be IHave"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_cov_notification_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x02, 0x09, 0x12, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x0a,
+            0x39, 0x00, 0x4e, 0x09, 0x55, 0x2e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x2f, 0x09, 0x6f,
+            0x2e, 0x82, 0x04, 0x00, 0x2f, 0x4f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedCOVNotification(cov) => {
+                assert_eq!(cov.subscriber_process_id, 18);
+                assert_eq!(
+                    cov.initiating_device_id.object_type,
+                    ObjectType::ObjectDevice
+                );
+                assert_eq!(cov.initiating_device_id.instance, 4);
+                assert_eq!(
+                    cov.monitored_object_id.object_type,
+                    ObjectType::ObjectAnalogInput
+                );
+                assert_eq!(cov.monitored_object_id.instance, 10);
+                assert_eq!(cov.time_remaining, 0);
+                assert_eq!(cov.list_of_values, &bytes[17..33]);
+                let mut values = cov.values();
+                let value = values.next().unwrap().unwrap();
+                assert_eq!(value.property_id, PropertyId::PropPresentValue);
+                assert_eq!(
+                    value.values().next().unwrap().unwrap(),
+                    ApplicationValue::Real(72.0)
+                );
+                let value = values.next().unwrap().unwrap();
+                assert_eq!(value.property_id, PropertyId::PropStatusFlags);
+                match value.values().next().unwrap().unwrap() {
+                    ApplicationValue::BitString(flags) => {
+                        assert_eq!(flags.len(), 4);
+                        assert_eq!(flags.bit(0), Some(false));
+                    }
+                    _ => panic!("should be a bit string"),
+                }
+                assert!(values.next().is_none());
+            }
+            _ => panic!("should be UnconfirmedCOVNotification"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_event_notification_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x03, 0x09, 0x01, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x0a,
+            0x3e, 0x2e, 0xa4, 0x7a, 0x0a, 0x12, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00, 0x2f, 0x3f,
+            0x49, 0x05, 0x59, 0x64, 0x69, 0x05, 0x7d, 0x06, 0x00, 0x48, 0x69, 0x67, 0x68, 0x21,
+            0x89, 0x00, 0x99, 0x01, 0xa9, 0x00, 0xb9, 0x03, 0xce, 0x5e, 0x0c, 0x42, 0xa0, 0x00,
+            0x00, 0x5f, 0xcf,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedEventNotification(event) => {
+                assert_eq!(event.subscriber_process_id, 1);
+                assert_eq!(event.initiating_device_id.instance, 4);
+                assert_eq!(event.event_object_id.instance, 10);
+                match event.timestamp {
+                    TimeStamp::DateTime(datetime) => {
+                        assert_eq!(datetime.date.year(), Some(2022));
+                        assert_eq!(datetime.date.month(), Some(10));
+                        assert_eq!(datetime.date.day(), Some(18));
+                        assert_eq!(datetime.time.hour(), Some(12));
+                        assert_eq!(datetime.time.minute(), Some(30));
+                    }
+                    _ => panic!("should be DateTime"),
+                }
+                assert_eq!(event.notification_class, 5);
+                assert_eq!(event.priority, 100);
+                assert_eq!(event.event_type, EventType::OutOfRange);
+                assert_eq!(event.message_text.unwrap().as_str(), Some("High!"));
+                assert_eq!(event.notify_type, NotifyType::Alarm);
+                assert_eq!(event.ack_required, Some(true));
+                assert_eq!(event.from_state, Some(EventState::Normal));
+                assert_eq!(event.to_state, EventState::HighLimit);
+                assert_eq!(event.event_values, Some(&bytes[51..58]));
+            }
+            _ => panic!("should be UnconfirmedEventNotification"),
+        }
+    }
+
+    #[test]
+    fn time_synchronization_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x06, 0xa4, 0x7a, 0x0a, 0x12, 0xff, 0xb4, 0x0c, 0x1e, 0x00, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::TimeSynchronization(datetime) => {
+                assert_eq!(datetime.date.year(), Some(2022));
+                assert_eq!(datetime.date.weekday(), None);
+                assert_eq!(datetime.time.hour(), Some(12));
+                assert_eq!(datetime.time.hundredths(), Some(0));
+            }
+            _ => panic!("should be TimeSynchronization"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_text_message_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x05, 0x0c, 0x02, 0x00, 0x00, 0x04, 0x1e, 0x09, 0x05, 0x1f, 0x29, 0x01, 0x3d,
+            0x06, 0x00, 0x48, 0x65, 0x6c, 0x6c, 0x6f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedTextMessage(msg) => {
+                assert_eq!(msg.source_device.instance, 4);
+                match msg.message_class {
+                    Some(MessageClass::Numeric(class)) => assert_eq!(class, 5),
+                    _ => panic!("should be Numeric"),
+                }
+                assert_eq!(msg.message_priority, MessagePriority::Urgent);
+                assert_eq!(msg.message.as_str(), Some("Hello"));
+            }
+            _ => panic!("should be UnconfirmedTextMessage"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_private_transfer_test() {
+        let bytes: &[u8] = &[0x10, 0x04, 0x09, 0x08, 0x19, 0x01, 0x2e, 0x21, 0x05, 0x2f];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedPrivateTransfer(pt) => {
+                assert_eq!(pt.vendor_id, 8);
+                assert_eq!(pt.service_number, 1);
+                assert_eq!(pt.service_parameters, Some(&bytes[7..9]));
+            }
+            _ => panic!("should be UnconfirmedPrivateTransfer"),
+        }
+    }
+
+    #[test]
+    fn write_group_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x0a, 0x09, 0x01, 0x19, 0x08, 0x2e, 0x09, 0x01, 0x44, 0x42, 0xc8, 0x00, 0x00,
+            0x2f, 0x39, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WriteGroup(wg) => {
+                assert_eq!(wg.group_number, 1);
+                assert_eq!(wg.write_priority, 8);
+                assert_eq!(wg.change_list, &bytes[7..14]);
+                assert_eq!(wg.inhibit_write, Some(false));
+            }
+            _ => panic!("should be WriteGroup"),
+        }
+    }
+
+    #[test]
+    fn unconfirmed_cov_notification_multiple_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x0b, 0x09, 0x01, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x29, 0x3c, 0x3e, 0xa4, 0x7a,
+            0x0a, 0x12, 0x04, 0xb4, 0x0c, 0x1e, 0x00, 0x00, 0x3f, 0x4e, 0x0c, 0x00, 0x00, 0x00,
+            0x0a, 0x1e, 0x09, 0x55, 0x2e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x2f, 0x1f, 0x4f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::UnconfirmedCOVNotificationMultiple(cov) => {
+                assert_eq!(cov.subscriber_process_id, 1);
+                assert_eq!(cov.initiating_device_id.instance, 4);
+                assert_eq!(cov.time_remaining, 60);
+                assert_eq!(cov.timestamp.unwrap().date.year(), Some(2022));
+                assert_eq!(cov.list_of_cov_notifications, &bytes[24..40]);
+            }
+            _ => panic!("should be UnconfirmedCOVNotificationMultiple"),
+        }
+    }
+
+    #[test]
+    fn who_am_i_you_are_test() {
+        let bytes: &[u8] = &[
+            0x10, 0x0d, 0x21, 0x08, 0x74, 0x00, 0x41, 0x42, 0x43, 0x74, 0x00, 0x31, 0x32, 0x33,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::WhoAmI(who) => {
+                assert_eq!(who.vendor_id, 8);
+                assert_eq!(who.model_name.as_str(), Some("ABC"));
+                assert_eq!(who.serial_number.as_str(), Some("123"));
+            }
+            _ => panic!("should be WhoAmI"),
+        }
+
+        let bytes: &[u8] = &[
+            0x10, 0x0e, 0x21, 0x08, 0x74, 0x00, 0x41, 0x42, 0x43, 0x74, 0x00, 0x31, 0x32, 0x33,
+            0xc4, 0x02, 0x00, 0x00, 0x07, 0x61, 0x0a,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::YouAre(you) => {
+                assert_eq!(you.vendor_id, 8);
+                assert_eq!(you.model_name.as_str(), Some("ABC"));
+                assert_eq!(you.serial_number.as_str(), Some("123"));
+                assert_eq!(you.device_id.unwrap().instance, 7);
+                assert_eq!(you.device_mac_address, Some(&[0x0a][..]));
+            }
+            _ => panic!("should be YouAre"),
+        }
+    }
+
+    #[test]
+    fn unknown_unconfirmed_service_test() {
+        let apdu = parse_apdu(&[0x10, 0x0f, 0x00]).unwrap();
+        match UnconfirmedServiceChoice::parse(&apdu).unwrap() {
+            UnconfirmedServiceChoice::Unknown(b) => assert_eq!(b, 0x0f),
+            _ => panic!("should be Unknown"),
+        }
+    }
+
+    #[test]
+    fn simple_ack_test() {
+        let bytes: &[u8] = &[0x20, 0x6a, 0x0f];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = SimpleACKPDU::parse(&apdu).unwrap();
+        assert_eq!(ack.invoke_id(), 0x6a);
+        assert_eq!(ack.service_choice(), ConfirmedServiceChoice::WriteProperty);
+        assert_eq!(ack.service_choice_byte(), 0x0f);
+        assert!(ComplexACKPDU::parse(&apdu).is_err());
+        let apdu = parse_apdu(&bytes[..2]).unwrap();
+        assert!(SimpleACKPDU::parse(&apdu).is_err());
+    }
+
+    #[test]
+    fn complex_ack_test() {
+        let bytes: &[u8] = &[
+            0x30, 0xc9, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x6f, 0x19, 0x4c, 0x29, 0x00, 0x3e, 0x21,
+            0x21, 0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexACKPDU::parse(&apdu).unwrap();
+        assert!(!ack.is_segmented_message());
+        assert!(!ack.is_more_follows());
+        assert_eq!(ack.invoke_id(), 0xc9);
+        assert_eq!(ack.sequence_number(), None);
+        assert_eq!(ack.proposed_window_size(), None);
+        assert_eq!(ack.service_choice(), ConfirmedServiceChoice::ReadProperty);
+        assert_eq!(ack.service_ack(), &bytes[3..]);
+
+        let bytes: &[u8] = &[0x3c, 0x01, 0x02, 0x04, 0x0e, 0x0c];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexACKPDU::parse(&apdu).unwrap();
+        assert!(ack.is_segmented_message());
+        assert!(ack.is_more_follows());
+        assert_eq!(ack.sequence_number(), Some(2));
+        assert_eq!(ack.proposed_window_size(), Some(4));
+        assert_eq!(
+            ack.service_choice(),
+            ConfirmedServiceChoice::ReadPropertyMultiple
+        );
+        assert_eq!(ack.service_ack(), &bytes[5..]);
+    }
+
+    #[test]
+    fn segment_ack_test() {
+        let bytes: &[u8] = &[0x43, 0x01, 0x02, 0x04];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = SegmentACKPDU::parse(&apdu).unwrap();
+        assert!(ack.is_nak());
+        assert!(ack.is_server());
+        assert_eq!(ack.invoke_id(), 1);
+        assert_eq!(ack.sequence_number(), 2);
+        assert_eq!(ack.actual_window_size(), 4);
+    }
+
+    #[test]
+    fn reject_test() {
+        let bytes: &[u8] = &[0x60, 0x01, 0x09];
+        let apdu = parse_apdu(bytes).unwrap();
+        let reject = RejectPDU::parse(&apdu).unwrap();
+        assert_eq!(reject.invoke_id(), 1);
+        assert_eq!(reject.reason(), BACnetRejectReason::UnrecognizedService);
+    }
+
+    #[test]
+    fn abort_test() {
+        let bytes: &[u8] = &[0x71, 0x01, 0x04];
+        let apdu = parse_apdu(bytes).unwrap();
+        let abort = AbortPDU::parse(&apdu).unwrap();
+        assert!(abort.is_server());
+        assert_eq!(abort.invoke_id(), 1);
+        assert_eq!(abort.reason(), AbortReason::SegmentationNotSupported);
+        let bytes: &[u8] = &[0x70, 0x01, 0x80];
+        let apdu = parse_apdu(bytes).unwrap();
+        let abort = AbortPDU::parse(&apdu).unwrap();
+        assert!(!abort.is_server());
+        assert_eq!(abort.reason(), AbortReason::Proprietary);
+    }
+
+    #[test]
+    fn error_test() {
+        let bytes: &[u8] = &[0x50, 0x01, 0x0c, 0x91, 0x02, 0x91, 0x20];
+        let apdu = parse_apdu(bytes).unwrap();
+        let err = ErrorPDU::parse(&apdu).unwrap();
+        assert_eq!(err.invoke_id(), 1);
+        assert_eq!(err.service_choice(), ConfirmedServiceChoice::ReadProperty);
+        assert_eq!(err.error_class(), ErrorClass::Property);
+        assert_eq!(err.error_code(), ErrorCode::UnknownProperty);
+        assert_eq!(err.details(), &ErrorDetails::None);
+
+        let apdu = parse_apdu(&bytes[..6]).unwrap();
+        assert!(ErrorPDU::parse(&apdu).is_err());
+    }
+
+    #[test]
+    fn create_object_error_test() {
+        let bytes: &[u8] = &[
+            0x50, 0x01, 0x0a, 0x0e, 0x91, 0x03, 0x91, 0x12, 0x0f, 0x19, 0x01,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let err = ErrorPDU::parse(&apdu).unwrap();
+        assert_eq!(err.error_class(), ErrorClass::Resources);
+        assert_eq!(err.error_code(), ErrorCode::NoSpaceForObject);
+        assert_eq!(
+            err.details(),
+            &ErrorDetails::CreateObject {
+                first_failed_element_number: 1
+            }
+        );
+    }
+
+    #[test]
+    fn write_property_multiple_error_test() {
+        let bytes: &[u8] = &[
+            0x50, 0x02, 0x10, 0x0e, 0x91, 0x02, 0x91, 0x28, 0x0f, 0x1e, 0x0c, 0x00, 0x40, 0x00,
+            0x01, 0x19, 0x55, 0x1f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let err = ErrorPDU::parse(&apdu).unwrap();
+        assert_eq!(
+            err.service_choice(),
+            ConfirmedServiceChoice::WritePropertyMultiple
+        );
+        assert_eq!(err.error_class(), ErrorClass::Property);
+        assert_eq!(err.error_code(), ErrorCode::WriteAccessDenied);
+        match err.details() {
+            ErrorDetails::WritePropertyMultiple {
+                first_failed_write_attempt,
+            } => {
+                let attempt = first_failed_write_attempt;
+                assert_eq!(
+                    attempt.object_id.object_type,
+                    ObjectType::ObjectAnalogOutput
+                );
+                assert_eq!(attempt.object_id.instance, 1);
+                assert_eq!(attempt.property_id, PropertyId::PropPresentValue);
+                assert_eq!(attempt.array_index, None);
+            }
+            _ => panic!("should be WritePropertyMultiple"),
+        }
+    }
+
+    #[test]
+    fn private_transfer_and_change_list_error_test() {
+        let bytes: &[u8] = &[
+            0x50, 0x03, 0x12, 0x0e, 0x91, 0x05, 0x91, 0x1d, 0x0f, 0x19, 0x08, 0x29, 0x01, 0x3e,
+            0x21, 0x05, 0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let err = ErrorPDU::parse(&apdu).unwrap();
+        assert_eq!(err.error_class(), ErrorClass::Services);
+        assert_eq!(err.error_code(), ErrorCode::ServiceRequestDenied);
+        assert_eq!(
+            err.details(),
+            &ErrorDetails::ConfirmedPrivateTransfer {
+                vendor_id: 8,
+                service_number: 1,
+                error_parameters: Some(&bytes[14..16]),
+            }
+        );
+
+        let bytes: &[u8] = &[
+            0x50, 0x04, 0x08, 0x0e, 0x91, 0x02, 0x91, 0x51, 0x0f, 0x19, 0x02,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let err = ErrorPDU::parse(&apdu).unwrap();
+        assert_eq!(err.error_code(), ErrorCode::ListElementNotFound);
+        assert_eq!(
+            err.details(),
+            &ErrorDetails::ChangeList {
+                first_failed_element_number: 2
+            }
+        );
+    }
+
+    #[test]
+    fn confirmed_service_choice_round_trip_test() {
+        for b in 0..=255u8 {
+            assert_eq!(u8::from(ConfirmedServiceChoice::from(b)), b);
+        }
+        assert_eq!(
+            ConfirmedServiceChoice::from(0x06),
+            ConfirmedServiceChoice::AtomicReadFile
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::from(0x1a),
+            ConfirmedServiceChoice::ReadRange
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::from(0x21),
+            ConfirmedServiceChoice::AuditLogQuery
+        );
+        assert_eq!(
+            ConfirmedServiceChoice::from(0x22),
+            ConfirmedServiceChoice::Unknown(0x22)
+        );
+    }
+
+    #[test]
+    fn apdu_confirmed_service_choice_test() {
+        let cases: &[(&[u8], Option<ConfirmedServiceChoice>)] = &[
+            (
+                &[0x02, 0x01, 0x6a, 0x0f],
+                Some(ConfirmedServiceChoice::WriteProperty),
+            ),
+            (
+                &[0x0a, 0x75, 0x01, 0x00, 0x04, 0x0a],
+                Some(ConfirmedServiceChoice::CreateObject),
+            ),
+            (
+                &[0x20, 0x01, 0x00],
+                Some(ConfirmedServiceChoice::AcknowledgeAlarm),
+            ),
+            (&[0x30, 0x01, 0x1a], Some(ConfirmedServiceChoice::ReadRange)),
+            (
+                &[0x38, 0x01, 0x00, 0x04, 0x06],
+                Some(ConfirmedServiceChoice::AtomicReadFile),
+            ),
+            (
+                &[0x50, 0x01, 0x0b, 0x91, 0x01, 0x91, 0x17],
+                Some(ConfirmedServiceChoice::DeleteObject),
+            ),
+            (&[0x10, 0x08], None),
+            (&[0x60, 0x01, 0x09], None),
+            (&[0x02, 0x01, 0x6a], None),
+        ];
+        for (bytes, choice) in cases {
+            let apdu = parse_apdu(bytes).unwrap();
+            assert_eq!(apdu.confirmed_service_choice(), *choice);
+        }
+    }
 }
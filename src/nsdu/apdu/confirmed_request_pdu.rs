@@ -0,0 +1,176 @@
+use super::{ConfirmedServiceChoice, PDUType, APDU};
+use crate::Error;
+
+/// The header of a BACnet-Confirmed-Request-PDU, followed by the service request.
+pub struct ConfirmedRequestHeader<'a> {
+    control: u8,
+    max_segments_apdu: u8,
+    invoke_id: u8,
+    sequence_number: Option<u8>,
+    proposed_window_size: Option<u8>,
+    service_choice: u8,
+    service_request: &'a [u8],
+}
+
+impl<'a> ConfirmedRequestHeader<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
+        let bytes = apdu.bytes;
+        if !matches!(apdu.pdu_type(), PDUType::BACnetConfirmedRequestPDU) {
+            return Err(Error::InvalidValue("not a confirmed request pdu"));
+        }
+        // The sequence number and proposed window size are only present in segmented messages.
+        let segmented = bytes[0] & 0x08 != 0;
+        let service_choice_idx = if segmented { 5 } else { 3 };
+        if bytes.len() <= service_choice_idx {
+            return Err(Error::Length(
+                "insufficient size for confirmed request header",
+            ));
+        }
+        Ok(Self {
+            control: bytes[0],
+            max_segments_apdu: bytes[1],
+            invoke_id: bytes[2],
+            sequence_number: if segmented { Some(bytes[3]) } else { None },
+            proposed_window_size: if segmented { Some(bytes[4]) } else { None },
+            service_choice: bytes[service_choice_idx],
+            service_request: &bytes[service_choice_idx + 1..],
+        })
+    }
+
+    pub fn is_segmented_message(&self) -> bool {
+        self.control & 0x08 != 0
+    }
+
+    pub fn is_more_follows(&self) -> bool {
+        self.control & 0x04 != 0
+    }
+
+    pub fn is_segmented_response_accepted(&self) -> bool {
+        self.control & 0x02 != 0
+    }
+
+    pub fn max_segments_accepted(&self) -> MaxSegments {
+        (self.max_segments_apdu >> 4).into()
+    }
+
+    /// Returns the maximum APDU size accepted in octets, or `None` if the encoded value is
+    /// reserved.
+    pub fn max_apdu_length_accepted(&self) -> Option<u16> {
+        match self.max_segments_apdu & 0x0F {
+            0 => Some(50),
+            1 => Some(128),
+            2 => Some(206),
+            3 => Some(480),
+            4 => Some(1024),
+            5 => Some(1476),
+            _ => None,
+        }
+    }
+
+    pub fn invoke_id(&self) -> u8 {
+        self.invoke_id
+    }
+
+    /// Only present if this is a segmented message.
+    pub fn sequence_number(&self) -> Option<u8> {
+        self.sequence_number
+    }
+
+    /// Only present if this is a segmented message.
+    pub fn proposed_window_size(&self) -> Option<u8> {
+        self.proposed_window_size
+    }
+
+    pub fn service_choice(&self) -> ConfirmedServiceChoice {
+        self.service_choice.into()
+    }
+
+    pub fn service_choice_byte(&self) -> u8 {
+        self.service_choice
+    }
+
+    /// The service request bytes after the header.
+    pub fn service_request(&self) -> &'a [u8] {
+        self.service_request
+    }
+}
+
+/// The maximum number of segments in a segmented response that a device will accept.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxSegments {
+    Unspecified,
+    Segments(u8),
+    MoreThan64,
+}
+
+impl From<u8> for MaxSegments {
+    fn from(b: u8) -> Self {
+        match b & 0x07 {
+            0 => Self::Unspecified,
+            7 => Self::MoreThan64,
+            b => Self::Segments(1 << b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::parse_apdu;
+
+    #[test]
+    fn unsegmented_header_test() {
+        let bytes: &[u8] = &[
+            0x02, 0x01, 0x6a, 0x0f, 0x0c, 0x00, 0x80, 0x00, 0x0a, 0x19, 0x55, 0x3e, 0x44, 0x41,
+            0xe8, 0x00, 0x01, 0x3f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert!(!header.is_segmented_message());
+        assert!(!header.is_more_follows());
+        assert!(header.is_segmented_response_accepted());
+        assert_eq!(header.max_segments_accepted(), MaxSegments::Unspecified);
+        assert_eq!(header.max_apdu_length_accepted(), Some(128));
+        assert_eq!(header.invoke_id(), 0x6a);
+        assert_eq!(header.sequence_number(), None);
+        assert_eq!(header.proposed_window_size(), None);
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::WriteProperty
+        );
+        assert_eq!(header.service_request(), &bytes[4..]);
+    }
+
+    #[test]
+    fn segmented_header_test() {
+        let bytes: &[u8] = &[
+            0x0e, 0x75, 0x01, 0x00, 0x04, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x01,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert!(header.is_segmented_message());
+        assert!(header.is_more_follows());
+        assert!(header.is_segmented_response_accepted());
+        assert_eq!(header.max_segments_accepted(), MaxSegments::MoreThan64);
+        assert_eq!(header.max_apdu_length_accepted(), Some(1476));
+        assert_eq!(header.invoke_id(), 1);
+        assert_eq!(header.sequence_number(), Some(0));
+        assert_eq!(header.proposed_window_size(), Some(4));
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::ReadProperty
+        );
+        assert_eq!(header.service_request(), &bytes[6..]);
+
+        let bytes: &[u8] = &[0x24, 0x05, 0x01, 0x0c];
+        let apdu = parse_apdu(bytes).unwrap();
+        assert!(ConfirmedRequestHeader::parse(&apdu).is_err());
+        let bytes: &[u8] = &[0x08, 0x05, 0x01, 0x0c];
+        let apdu = parse_apdu(bytes).unwrap();
+        assert!(ConfirmedRequestHeader::parse(&apdu).is_err());
+        let bytes: &[u8] = &[0x00, 0x35, 0x01, 0x0c];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(header.max_segments_accepted(), MaxSegments::Segments(8));
+    }
+}
@@ -0,0 +1,246 @@
+use super::{
+    read_property_multiple::PropertyReference,
+    tag::{parse_context_tagged, parse_optional_context_tagged, Tag},
+    value::{parse_boolean, parse_real, DateTime, PropertyValues},
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned};
+use crate::Error;
+
+/// The parameters of a SubscribeCOV service request.
+pub struct SubscribeCOVRequest {
+    pub subscriber_process_id: u32,
+    pub monitored_object_id: ObjectId,
+    /// `None` along with `lifetime` when the request cancels a subscription.
+    pub issue_confirmed_notifications: Option<bool>,
+    /// The lifetime in seconds, where 0 is indefinite.
+    pub lifetime: Option<u32>,
+}
+
+impl SubscribeCOVRequest {
+    /// Attempt to parse a SubscribeCOVRequest from the service request bytes of a confirmed
+    /// request, i.e. `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, monitored_object_id) = parse_context_tagged(bytes, 1, ObjectId::parse)?;
+        let (bytes, issue_confirmed_notifications) =
+            parse_optional_context_tagged(bytes, 2, parse_boolean)?;
+        let (_, lifetime) = parse_optional_context_tagged(bytes, 3, parse_unsigned)?;
+        Ok(Self {
+            subscriber_process_id,
+            monitored_object_id,
+            issue_confirmed_notifications,
+            lifetime,
+        })
+    }
+
+    /// Returns true if the request cancels an existing subscription.
+    pub fn is_cancellation(&self) -> bool {
+        self.issue_confirmed_notifications.is_none() && self.lifetime.is_none()
+    }
+}
+
+/// The parameters of a SubscribeCOVProperty service request.
+pub struct SubscribeCOVPropertyRequest {
+    pub subscriber_process_id: u32,
+    pub monitored_object_id: ObjectId,
+    /// `None` along with `lifetime` when the request cancels a subscription.
+    pub issue_confirmed_notifications: Option<bool>,
+    /// The lifetime in seconds, where 0 is indefinite.
+    pub lifetime: Option<u32>,
+    pub monitored_property: PropertyReference,
+    pub cov_increment: Option<f32>,
+}
+
+impl SubscribeCOVPropertyRequest {
+    /// Attempt to parse a SubscribeCOVPropertyRequest from the service request bytes of a
+    /// confirmed request, i.e. `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, monitored_object_id) = parse_context_tagged(bytes, 1, ObjectId::parse)?;
+        let (bytes, issue_confirmed_notifications) =
+            parse_optional_context_tagged(bytes, 2, parse_boolean)?;
+        let (bytes, lifetime) = parse_optional_context_tagged(bytes, 3, parse_unsigned)?;
+        let (bytes, monitored_property) = Tag::parse_constructed(bytes, 4)?;
+        let (_, monitored_property) = PropertyReference::parse(monitored_property)?;
+        let (_, cov_increment) = parse_optional_context_tagged(bytes, 5, parse_real)?;
+        Ok(Self {
+            subscriber_process_id,
+            monitored_object_id,
+            issue_confirmed_notifications,
+            lifetime,
+            monitored_property,
+            cov_increment,
+        })
+    }
+
+    /// Returns true if the request cancels an existing subscription.
+    pub fn is_cancellation(&self) -> bool {
+        self.issue_confirmed_notifications.is_none() && self.lifetime.is_none()
+    }
+}
+
+/// The parameters of a (Un)confirmedCOVNotification service request.
+pub struct COVNotification<'a> {
+    pub subscriber_process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub monitored_object_id: ObjectId,
+    pub time_remaining: u32,
+    /// The encoded list of BACnetPropertyValue, excluding the enclosing tags.
+    pub list_of_values: &'a [u8],
+}
+
+impl<'a> COVNotification<'a> {
+    /// Attempt to parse a COVNotification from the service request bytes. For a
+    /// ConfirmedCOVNotification these are `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, initiating_device_id) = parse_context_tagged(bytes, 1, ObjectId::parse)?;
+        let (bytes, monitored_object_id) = parse_context_tagged(bytes, 2, ObjectId::parse)?;
+        let (bytes, time_remaining) = parse_context_tagged(bytes, 3, parse_unsigned)?;
+        let (_, list_of_values) = Tag::parse_constructed(bytes, 4)?;
+        Ok(Self {
+            subscriber_process_id,
+            initiating_device_id,
+            monitored_object_id,
+            time_remaining,
+            list_of_values,
+        })
+    }
+
+    /// Iterate over the reported property values.
+    pub fn values(&self) -> PropertyValues<'a> {
+        self.list_of_values.into()
+    }
+}
+
+/// The parameters of a (Un)confirmedCOVNotificationMultiple service request.
+pub struct COVNotificationMultiple<'a> {
+    pub subscriber_process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub time_remaining: u32,
+    pub timestamp: Option<DateTime>,
+    /// The encoded list of COV notifications, excluding the enclosing tags.
+    pub list_of_cov_notifications: &'a [u8],
+}
+
+impl<'a> COVNotificationMultiple<'a> {
+    /// Attempt to parse a COVNotificationMultiple from the service request bytes. For a
+    /// ConfirmedCOVNotificationMultiple these are `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, initiating_device_id) = parse_context_tagged(bytes, 1, ObjectId::parse)?;
+        let (bytes, time_remaining) = parse_context_tagged(bytes, 2, parse_unsigned)?;
+        let (bytes, timestamp) = if Tag::is_opening(bytes, 3) {
+            let (rest, timestamp) = Tag::parse_constructed(bytes, 3)?;
+            (rest, Some(DateTime::parse(timestamp)?.1))
+        } else {
+            (bytes, None)
+        };
+        let (_, list_of_cov_notifications) = Tag::parse_constructed(bytes, 4)?;
+        Ok(Self {
+            subscriber_process_id,
+            initiating_device_id,
+            time_remaining,
+            timestamp,
+            list_of_cov_notifications,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::{
+        confirmed_request_pdu::ConfirmedRequestHeader, value::ApplicationValue,
+        ConfirmedServiceChoice,
+    };
+    use crate::nsdu::{object_type::ObjectType, parse_apdu, property_id::PropertyId};
+
+    #[test]
+    fn subscribe_cov_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x0f, 0x05, // header
+            0x09, 0x12, 0x1c, 0x00, 0x00, 0x00, 0x0a, 0x29, 0x01, 0x39, 0x78,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::SubscribeCOV
+        );
+        let sub = SubscribeCOVRequest::parse(header.service_request()).unwrap();
+        assert_eq!(sub.subscriber_process_id, 18);
+        assert_eq!(
+            sub.monitored_object_id.object_type,
+            ObjectType::ObjectAnalogInput
+        );
+        assert_eq!(sub.monitored_object_id.instance, 10);
+        assert_eq!(sub.issue_confirmed_notifications, Some(true));
+        assert_eq!(sub.lifetime, Some(120));
+        assert!(!sub.is_cancellation());
+
+        let sub = SubscribeCOVRequest::parse(&header.service_request()[..7]).unwrap();
+        assert_eq!(sub.issue_confirmed_notifications, None);
+        assert_eq!(sub.lifetime, None);
+        assert!(sub.is_cancellation());
+    }
+
+    #[test]
+    fn subscribe_cov_property_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x10, 0x1c, // header
+            0x09, 0x12, 0x1c, 0x00, 0x00, 0x00, 0x0a, 0x29, 0x00, 0x39, 0x00, 0x4e, 0x09, 0x55,
+            0x4f, 0x5c, 0x3f, 0x00, 0x00, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::SubscribeCOVProperty
+        );
+        let sub = SubscribeCOVPropertyRequest::parse(header.service_request()).unwrap();
+        assert_eq!(sub.subscriber_process_id, 18);
+        assert_eq!(sub.issue_confirmed_notifications, Some(false));
+        assert_eq!(sub.lifetime, Some(0));
+        assert_eq!(
+            sub.monitored_property.property_id,
+            PropertyId::PropPresentValue
+        );
+        assert_eq!(sub.monitored_property.array_index, None);
+        assert_eq!(sub.cov_increment, Some(0.5));
+
+        let sub = SubscribeCOVPropertyRequest::parse(&header.service_request()[..15]).unwrap();
+        assert_eq!(sub.cov_increment, None);
+        assert!(SubscribeCOVPropertyRequest::parse(&header.service_request()[..11]).is_err());
+    }
+
+    #[test]
+    fn confirmed_cov_notification_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x11, 0x01, // header
+            0x09, 0x12, 0x1c, 0x02, 0x00, 0x00, 0x04, 0x2c, 0x00, 0x00, 0x00, 0x0a, 0x39, 0x3c,
+            0x4e, 0x09, 0x55, 0x2e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x2f, 0x4f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::ConfirmedCOVNotification
+        );
+        let cov = COVNotification::parse(header.service_request()).unwrap();
+        assert_eq!(cov.subscriber_process_id, 18);
+        assert_eq!(cov.initiating_device_id.instance, 4);
+        assert_eq!(cov.monitored_object_id.instance, 10);
+        assert_eq!(cov.time_remaining, 60);
+        let mut values = cov.values();
+        let value = values.next().unwrap().unwrap();
+        assert_eq!(value.property_id, PropertyId::PropPresentValue);
+        assert_eq!(value.array_index, None);
+        assert_eq!(value.priority, None);
+        assert_eq!(
+            value.values().next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        assert!(values.next().is_none());
+    }
+}
@@ -0,0 +1,374 @@
+use super::tag::{parse_application_tagged, TagType};
+use crate::nsdu::parse_unsigned;
+use crate::Error;
+
+/// A BACnet Error, which is an application tagged error class followed by an application tagged
+/// error code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BACnetError {
+    pub error_class: ErrorClass,
+    pub error_code: ErrorCode,
+}
+
+impl BACnetError {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, error_class) =
+            parse_application_tagged(bytes, TagType::Enumerated, parse_unsigned)?;
+        let (bytes, error_code) =
+            parse_application_tagged(bytes, TagType::Enumerated, parse_unsigned)?;
+        Ok((
+            bytes,
+            Self {
+                error_class: error_class.into(),
+                error_code: error_code.into(),
+            },
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorClass {
+    Device,
+    Object,
+    Property,
+    Resources,
+    Security,
+    Services,
+    VT,
+    Communication,
+    Reserved(u32),
+    Proprietary(u32),
+}
+
+impl From<u32> for ErrorClass {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::Device,
+            1 => Self::Object,
+            2 => Self::Property,
+            3 => Self::Resources,
+            4 => Self::Security,
+            5 => Self::Services,
+            6 => Self::VT,
+            7 => Self::Communication,
+            8..=63 => Self::Reserved(b),
+            _ => Self::Proprietary(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorCode {
+    Other,
+    AuthenticationFailed,
+    ConfigurationInProgress,
+    DeviceBusy,
+    DynamicCreationNotSupported,
+    FileAccessDenied,
+    IncompatibleSecurityLevels,
+    InconsistentParameters,
+    InconsistentSelectionCriterion,
+    InvalidDataType,
+    InvalidFileAccessMethod,
+    InvalidFileStartPosition,
+    InvalidOperatorName,
+    InvalidParameterDataType,
+    InvalidTimeStamp,
+    KeyGenerationError,
+    MissingRequiredParameter,
+    NoObjectsOfSpecifiedType,
+    NoSpaceForObject,
+    NoSpaceToAddListElement,
+    NoSpaceToWriteProperty,
+    NoVTSessionsAvailable,
+    PropertyIsNotAList,
+    ObjectDeletionNotPermitted,
+    ObjectIdentifierAlreadyExists,
+    OperationalProblem,
+    PasswordFailure,
+    ReadAccessDenied,
+    SecurityNotSupported,
+    ServiceRequestDenied,
+    Timeout,
+    UnknownObject,
+    UnknownProperty,
+    UnknownVTClass,
+    UnknownVTSession,
+    UnsupportedObjectType,
+    ValueOutOfRange,
+    VTSessionAlreadyClosed,
+    VTSessionTerminationFailure,
+    WriteAccessDenied,
+    CharacterSetNotSupported,
+    InvalidArrayIndex,
+    COVSubscriptionFailed,
+    NotCOVProperty,
+    OptionalFunctionalityNotSupported,
+    InvalidConfigurationData,
+    DatatypeNotSupported,
+    DuplicateName,
+    DuplicateObjectId,
+    PropertyIsNotAnArray,
+    AbortBufferOverflow,
+    AbortInvalidAPDUInThisState,
+    AbortPreemptedByHigherPriorityTask,
+    AbortSegmentationNotSupported,
+    AbortProprietary,
+    AbortOther,
+    InvalidTag,
+    NetworkDown,
+    RejectBufferOverflow,
+    RejectInconsistentParameters,
+    RejectInvalidParameterDataType,
+    RejectInvalidTag,
+    RejectMissingRequiredParameter,
+    RejectParameterOutOfRange,
+    RejectTooManyArguments,
+    RejectUndefinedEnumeration,
+    RejectUnrecognizedService,
+    RejectProprietary,
+    RejectOther,
+    UnknownDevice,
+    UnknownRoute,
+    ValueNotInitialized,
+    InvalidEventState,
+    NoAlarmConfigured,
+    LogBufferFull,
+    LoggedValuePurged,
+    NoPropertySpecified,
+    NotConfiguredForTriggeredLogging,
+    UnknownSubscription,
+    ParameterOutOfRange,
+    ListElementNotFound,
+    Busy,
+    CommunicationDisabled,
+    Success,
+    AccessDenied,
+    BadDestinationAddress,
+    BadDestinationDeviceId,
+    BadSignature,
+    BadSourceAddress,
+    BadTimestamp,
+    CannotUseKey,
+    CannotVerifyMessageId,
+    CorrectKeyRevision,
+    DestinationDeviceIdRequired,
+    DuplicateMessage,
+    EncryptionNotConfigured,
+    EncryptionRequired,
+    IncorrectKey,
+    InvalidKeyData,
+    KeyUpdateInProgress,
+    MalformedMessage,
+    NotKeyServer,
+    SecurityNotConfigured,
+    SourceSecurityRequired,
+    TooManyKeys,
+    UnknownAuthenticationType,
+    UnknownKey,
+    UnknownKeyRevision,
+    UnknownSourceMessage,
+    NotRouterToDnet,
+    RouterBusy,
+    UnknownNetworkMessage,
+    MessageTooLong,
+    SecurityError,
+    AddressingError,
+    WriteBDTFailed,
+    ReadBDTFailed,
+    RegisterForeignDeviceFailed,
+    ReadFDTFailed,
+    DeleteFDTEntryFailed,
+    DistributeBroadcastFailed,
+    UnknownFileSize,
+    AbortAPDUTooLong,
+    AbortApplicationExceededReplyTime,
+    AbortOutOfResources,
+    AbortTSMTimeout,
+    AbortWindowSizeOutOfRange,
+    FileFull,
+    InconsistentConfiguration,
+    InconsistentObjectType,
+    InternalError,
+    NotConfigured,
+    OutOfMemory,
+    ValueTooLong,
+    AbortInsufficientSecurity,
+    AbortSecurityError,
+    DuplicateEntry,
+    InvalidValueInThisState,
+    InvalidOperationInThisState,
+    ListItemNotNumbered,
+    ListItemNotTimestamped,
+    InvalidDataEncoding,
+    BVLCFunctionUnknown,
+    BVLCProprietaryFunctionUnknown,
+    HeaderEncodingError,
+    HeaderNotUnderstood,
+    MessageIncomplete,
+    NotABACnetSCHub,
+    PayloadExpected,
+    UnexpectedData,
+    NodeDuplicateVMAC,
+    /// Values up to 255 are reserved for ASHRAE, values above are proprietary.
+    Unknown(u32),
+}
+
+impl From<u32> for ErrorCode {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::Other,
+            1 => Self::AuthenticationFailed,
+            2 => Self::ConfigurationInProgress,
+            3 => Self::DeviceBusy,
+            4 => Self::DynamicCreationNotSupported,
+            5 => Self::FileAccessDenied,
+            6 => Self::IncompatibleSecurityLevels,
+            7 => Self::InconsistentParameters,
+            8 => Self::InconsistentSelectionCriterion,
+            9 => Self::InvalidDataType,
+            10 => Self::InvalidFileAccessMethod,
+            11 => Self::InvalidFileStartPosition,
+            12 => Self::InvalidOperatorName,
+            13 => Self::InvalidParameterDataType,
+            14 => Self::InvalidTimeStamp,
+            15 => Self::KeyGenerationError,
+            16 => Self::MissingRequiredParameter,
+            17 => Self::NoObjectsOfSpecifiedType,
+            18 => Self::NoSpaceForObject,
+            19 => Self::NoSpaceToAddListElement,
+            20 => Self::NoSpaceToWriteProperty,
+            21 => Self::NoVTSessionsAvailable,
+            22 => Self::PropertyIsNotAList,
+            23 => Self::ObjectDeletionNotPermitted,
+            24 => Self::ObjectIdentifierAlreadyExists,
+            25 => Self::OperationalProblem,
+            26 => Self::PasswordFailure,
+            27 => Self::ReadAccessDenied,
+            28 => Self::SecurityNotSupported,
+            29 => Self::ServiceRequestDenied,
+            30 => Self::Timeout,
+            31 => Self::UnknownObject,
+            32 => Self::UnknownProperty,
+            34 => Self::UnknownVTClass,
+            35 => Self::UnknownVTSession,
+            36 => Self::UnsupportedObjectType,
+            37 => Self::ValueOutOfRange,
+            38 => Self::VTSessionAlreadyClosed,
+            39 => Self::VTSessionTerminationFailure,
+            40 => Self::WriteAccessDenied,
+            41 => Self::CharacterSetNotSupported,
+            42 => Self::InvalidArrayIndex,
+            43 => Self::COVSubscriptionFailed,
+            44 => Self::NotCOVProperty,
+            45 => Self::OptionalFunctionalityNotSupported,
+            46 => Self::InvalidConfigurationData,
+            47 => Self::DatatypeNotSupported,
+            48 => Self::DuplicateName,
+            49 => Self::DuplicateObjectId,
+            50 => Self::PropertyIsNotAnArray,
+            51 => Self::AbortBufferOverflow,
+            52 => Self::AbortInvalidAPDUInThisState,
+            53 => Self::AbortPreemptedByHigherPriorityTask,
+            54 => Self::AbortSegmentationNotSupported,
+            55 => Self::AbortProprietary,
+            56 => Self::AbortOther,
+            57 => Self::InvalidTag,
+            58 => Self::NetworkDown,
+            59 => Self::RejectBufferOverflow,
+            60 => Self::RejectInconsistentParameters,
+            61 => Self::RejectInvalidParameterDataType,
+            62 => Self::RejectInvalidTag,
+            63 => Self::RejectMissingRequiredParameter,
+            64 => Self::RejectParameterOutOfRange,
+            65 => Self::RejectTooManyArguments,
+            66 => Self::RejectUndefinedEnumeration,
+            67 => Self::RejectUnrecognizedService,
+            68 => Self::RejectProprietary,
+            69 => Self::RejectOther,
+            70 => Self::UnknownDevice,
+            71 => Self::UnknownRoute,
+            72 => Self::ValueNotInitialized,
+            73 => Self::InvalidEventState,
+            74 => Self::NoAlarmConfigured,
+            75 => Self::LogBufferFull,
+            76 => Self::LoggedValuePurged,
+            77 => Self::NoPropertySpecified,
+            78 => Self::NotConfiguredForTriggeredLogging,
+            79 => Self::UnknownSubscription,
+            80 => Self::ParameterOutOfRange,
+            81 => Self::ListElementNotFound,
+            82 => Self::Busy,
+            83 => Self::CommunicationDisabled,
+            84 => Self::Success,
+            85 => Self::AccessDenied,
+            86 => Self::BadDestinationAddress,
+            87 => Self::BadDestinationDeviceId,
+            88 => Self::BadSignature,
+            89 => Self::BadSourceAddress,
+            90 => Self::BadTimestamp,
+            91 => Self::CannotUseKey,
+            92 => Self::CannotVerifyMessageId,
+            93 => Self::CorrectKeyRevision,
+            94 => Self::DestinationDeviceIdRequired,
+            95 => Self::DuplicateMessage,
+            96 => Self::EncryptionNotConfigured,
+            97 => Self::EncryptionRequired,
+            98 => Self::IncorrectKey,
+            99 => Self::InvalidKeyData,
+            100 => Self::KeyUpdateInProgress,
+            101 => Self::MalformedMessage,
+            102 => Self::NotKeyServer,
+            103 => Self::SecurityNotConfigured,
+            104 => Self::SourceSecurityRequired,
+            105 => Self::TooManyKeys,
+            106 => Self::UnknownAuthenticationType,
+            107 => Self::UnknownKey,
+            108 => Self::UnknownKeyRevision,
+            109 => Self::UnknownSourceMessage,
+            110 => Self::NotRouterToDnet,
+            111 => Self::RouterBusy,
+            112 => Self::UnknownNetworkMessage,
+            113 => Self::MessageTooLong,
+            114 => Self::SecurityError,
+            115 => Self::AddressingError,
+            116 => Self::WriteBDTFailed,
+            117 => Self::ReadBDTFailed,
+            118 => Self::RegisterForeignDeviceFailed,
+            119 => Self::ReadFDTFailed,
+            120 => Self::DeleteFDTEntryFailed,
+            121 => Self::DistributeBroadcastFailed,
+            122 => Self::UnknownFileSize,
+            123 => Self::AbortAPDUTooLong,
+            124 => Self::AbortApplicationExceededReplyTime,
+            125 => Self::AbortOutOfResources,
+            126 => Self::AbortTSMTimeout,
+            127 => Self::AbortWindowSizeOutOfRange,
+            128 => Self::FileFull,
+            129 => Self::InconsistentConfiguration,
+            130 => Self::InconsistentObjectType,
+            131 => Self::InternalError,
+            132 => Self::NotConfigured,
+            133 => Self::OutOfMemory,
+            134 => Self::ValueTooLong,
+            135 => Self::AbortInsufficientSecurity,
+            136 => Self::AbortSecurityError,
+            137 => Self::DuplicateEntry,
+            138 => Self::InvalidValueInThisState,
+            139 => Self::InvalidOperationInThisState,
+            140 => Self::ListItemNotNumbered,
+            141 => Self::ListItemNotTimestamped,
+            142 => Self::InvalidDataEncoding,
+            143 => Self::BVLCFunctionUnknown,
+            144 => Self::BVLCProprietaryFunctionUnknown,
+            145 => Self::HeaderEncodingError,
+            146 => Self::HeaderNotUnderstood,
+            147 => Self::MessageIncomplete,
+            148 => Self::NotABACnetSCHub,
+            149 => Self::PayloadExpected,
+            150 => Self::UnexpectedData,
+            151 => Self::NodeDuplicateVMAC,
+            b => Self::Unknown(b),
+        }
+    }
+}
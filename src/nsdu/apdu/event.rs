@@ -0,0 +1,205 @@
+use super::{
+    tag::{parse_context_tagged, parse_optional_context_tagged, Tag},
+    value::{parse_boolean, CharacterString, TimeStamp},
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned, parse_unsigned8};
+use crate::Error;
+
+/// The parameters of a (Un)confirmedEventNotification service request.
+pub struct EventNotification<'a> {
+    pub subscriber_process_id: u32,
+    pub initiating_device_id: ObjectId,
+    pub event_object_id: ObjectId,
+    pub timestamp: TimeStamp,
+    pub notification_class: u32,
+    pub priority: u8,
+    pub event_type: EventType,
+    pub message_text: Option<CharacterString<'a>>,
+    pub notify_type: NotifyType,
+    pub ack_required: Option<bool>,
+    pub from_state: Option<EventState>,
+    pub to_state: EventState,
+    /// The encoded BACnetNotificationParameters, excluding the enclosing tags.
+    pub event_values: Option<&'a [u8]>,
+}
+
+impl<'a> EventNotification<'a> {
+    /// Attempt to parse an EventNotification from the service request bytes. For a
+    /// ConfirmedEventNotification these are `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, subscriber_process_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, initiating_device_id) = parse_context_tagged(bytes, 1, ObjectId::parse)?;
+        let (bytes, event_object_id) = parse_context_tagged(bytes, 2, ObjectId::parse)?;
+        let (bytes, timestamp) = Tag::parse_constructed(bytes, 3)?;
+        let (_, timestamp) = TimeStamp::parse(timestamp)?;
+        let (bytes, notification_class) = parse_context_tagged(bytes, 4, parse_unsigned)?;
+        let (bytes, priority) = parse_context_tagged(bytes, 5, parse_unsigned8)?;
+        let (bytes, event_type) = parse_context_tagged(bytes, 6, parse_unsigned)?;
+        let (bytes, message_text) =
+            parse_optional_context_tagged(bytes, 7, CharacterString::parse)?;
+        let (bytes, notify_type) = parse_context_tagged(bytes, 8, parse_unsigned)?;
+        let (bytes, ack_required) = parse_optional_context_tagged(bytes, 9, parse_boolean)?;
+        let (bytes, from_state) = parse_optional_context_tagged(bytes, 10, parse_unsigned)?;
+        let (bytes, to_state) = parse_context_tagged(bytes, 11, parse_unsigned)?;
+        let event_values = if Tag::is_opening(bytes, 12) {
+            Some(Tag::parse_constructed(bytes, 12)?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            subscriber_process_id,
+            initiating_device_id,
+            event_object_id,
+            timestamp,
+            notification_class,
+            priority,
+            event_type: event_type.into(),
+            message_text,
+            notify_type: notify_type.into(),
+            ack_required,
+            from_state: from_state.map(EventState::from),
+            to_state: to_state.into(),
+            event_values,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventType {
+    ChangeOfBitstring,
+    ChangeOfState,
+    ChangeOfValue,
+    CommandFailure,
+    FloatingLimit,
+    OutOfRange,
+    ComplexEventType,
+    ChangeOfLifeSafety,
+    Extended,
+    BufferReady,
+    UnsignedRange,
+    AccessEvent,
+    DoubleOutOfRange,
+    SignedOutOfRange,
+    UnsignedOutOfRange,
+    ChangeOfCharacterstring,
+    ChangeOfStatusFlags,
+    ChangeOfReliability,
+    None,
+    ChangeOfDiscreteValue,
+    ChangeOfTimer,
+    Unknown(u32),
+}
+
+impl From<u32> for EventType {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::ChangeOfBitstring,
+            1 => Self::ChangeOfState,
+            2 => Self::ChangeOfValue,
+            3 => Self::CommandFailure,
+            4 => Self::FloatingLimit,
+            5 => Self::OutOfRange,
+            6 => Self::ComplexEventType,
+            8 => Self::ChangeOfLifeSafety,
+            9 => Self::Extended,
+            10 => Self::BufferReady,
+            11 => Self::UnsignedRange,
+            13 => Self::AccessEvent,
+            14 => Self::DoubleOutOfRange,
+            15 => Self::SignedOutOfRange,
+            16 => Self::UnsignedOutOfRange,
+            17 => Self::ChangeOfCharacterstring,
+            18 => Self::ChangeOfStatusFlags,
+            19 => Self::ChangeOfReliability,
+            20 => Self::None,
+            21 => Self::ChangeOfDiscreteValue,
+            22 => Self::ChangeOfTimer,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventState {
+    Normal,
+    Fault,
+    Offnormal,
+    HighLimit,
+    LowLimit,
+    LifeSafetyAlarm,
+    Unknown(u32),
+}
+
+impl From<u32> for EventState {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::Normal,
+            1 => Self::Fault,
+            2 => Self::Offnormal,
+            3 => Self::HighLimit,
+            4 => Self::LowLimit,
+            5 => Self::LifeSafetyAlarm,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotifyType {
+    Alarm,
+    Event,
+    AckNotification,
+    Unknown(u32),
+}
+
+impl From<u32> for NotifyType {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::Alarm,
+            1 => Self::Event,
+            2 => Self::AckNotification,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_type_test() {
+        assert_eq!(EventType::from(0), EventType::ChangeOfBitstring);
+        assert_eq!(EventType::from(2), EventType::ChangeOfValue);
+        assert_eq!(EventType::from(5), EventType::OutOfRange);
+        assert_eq!(EventType::from(9), EventType::Extended);
+        assert_eq!(EventType::from(20), EventType::None);
+        assert_eq!(EventType::from(22), EventType::ChangeOfTimer);
+        // 7 and 12 are deprecated
+        assert_eq!(EventType::from(7), EventType::Unknown(7));
+        assert_eq!(EventType::from(12), EventType::Unknown(12));
+        assert_eq!(EventType::from(23), EventType::Unknown(23));
+        assert_eq!(EventType::from(u32::MAX), EventType::Unknown(u32::MAX));
+    }
+
+    #[test]
+    fn notify_type_test() {
+        assert_eq!(NotifyType::from(0), NotifyType::Alarm);
+        assert_eq!(NotifyType::from(1), NotifyType::Event);
+        assert_eq!(NotifyType::from(2), NotifyType::AckNotification);
+        assert_eq!(NotifyType::from(3), NotifyType::Unknown(3));
+        assert_eq!(NotifyType::from(u32::MAX), NotifyType::Unknown(u32::MAX));
+    }
+
+    #[test]
+    fn event_state_test() {
+        assert_eq!(EventState::from(0), EventState::Normal);
+        assert_eq!(EventState::from(1), EventState::Fault);
+        assert_eq!(EventState::from(2), EventState::Offnormal);
+        assert_eq!(EventState::from(3), EventState::HighLimit);
+        assert_eq!(EventState::from(4), EventState::LowLimit);
+        assert_eq!(EventState::from(5), EventState::LifeSafetyAlarm);
+        assert_eq!(EventState::from(6), EventState::Unknown(6));
+        assert_eq!(EventState::from(u32::MAX), EventState::Unknown(u32::MAX));
+    }
+}
@@ -0,0 +1,158 @@
+use super::{
+    tag::{parse_context_tagged, parse_optional_context_tagged, Tag},
+    value::ApplicationValues,
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned, property_id::PropertyId};
+use crate::Error;
+
+/// The parameters of a ReadProperty service request.
+pub struct ReadPropertyRequest {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ReadPropertyRequest {
+    /// Attempt to parse a ReadPropertyRequest from the service request bytes of a confirmed
+    /// request, i.e. `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, property_id) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        let (_, array_index) = parse_optional_context_tagged(bytes, 2, parse_unsigned)?;
+        Ok(Self {
+            object_id,
+            property_id: property_id.into(),
+            array_index,
+        })
+    }
+}
+
+/// The parameters of a ReadProperty-ACK.
+pub struct ReadPropertyACK<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded property value, excluding the enclosing tags.
+    pub property_value: &'a [u8],
+}
+
+impl<'a> ReadPropertyACK<'a> {
+    /// Attempt to parse a ReadPropertyACK from the service ACK bytes of a complex ACK, i.e.
+    /// `ComplexACKPDU::service_ack()`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, property_id) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 2, parse_unsigned)?;
+        let (_, property_value) = Tag::parse_constructed(bytes, 3)?;
+        Ok(Self {
+            object_id,
+            property_id: property_id.into(),
+            array_index,
+            property_value,
+        })
+    }
+
+    /// Iterate over the property value as application tagged values.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.property_value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::{
+        confirmed_request_pdu::ConfirmedRequestHeader, value::ApplicationValue, ComplexACKPDU,
+    };
+    use crate::nsdu::{object_type::ObjectType, parse_apdu};
+    use crate::parse_bvlc;
+
+    #[test]
+    fn read_property_request_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x01, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x6f, 0x19, 0x4c, 0x29, 0x00,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        let rp = ReadPropertyRequest::parse(header.service_request()).unwrap();
+        assert_eq!(rp.object_id.object_type, ObjectType::ObjectDevice);
+        assert_eq!(rp.object_id.instance, 111);
+        assert_eq!(rp.property_id, PropertyId::PropObjectList);
+        assert_eq!(rp.array_index, Some(0));
+
+        let rp = ReadPropertyRequest::parse(&header.service_request()[..7]).unwrap();
+        assert_eq!(rp.array_index, None);
+        assert!(ReadPropertyRequest::parse(&header.service_request()[..6]).is_err());
+    }
+
+    #[test]
+    fn read_property_ack_test() {
+        let bytes: &[u8] = &[
+            0x81, 0x0a, 0x00, 0x1b, // BVLC
+            0x01, 0x20, 0x00, 0x0d, 0x01, 0x3d, 0xff, // NPDU
+            0x30, 0xc9, 0x0c, 0x0c, 0x02, 0x00, 0x00, 0x6f, 0x19, 0x4c, 0x29, 0x00, 0x3e, 0x21,
+            0x21, 0x3f, // APDU
+        ];
+        let bvlc = parse_bvlc(bytes).unwrap();
+        let npdu = bvlc.npdu().as_ref().unwrap();
+        let apdu = parse_apdu(npdu.payload()).unwrap();
+        let ack = ComplexACKPDU::parse(&apdu).unwrap();
+        let rp = ReadPropertyACK::parse(ack.service_ack()).unwrap();
+        assert_eq!(rp.object_id.instance, 111);
+        assert_eq!(rp.property_id, PropertyId::PropObjectList);
+        assert_eq!(rp.array_index, Some(0));
+        let mut values = rp.values();
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(33)
+        );
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn read_property_ack_values_test() {
+        let bytes: &[u8] = &[
+            0x0c, 0x02, 0x00, 0x00, 0x6f, 0x19, 0x4c, 0x3e, 0xc4, 0x02, 0x00, 0x00, 0x6f, 0xc4,
+            0x00, 0x00, 0x00, 0x01, 0x3f,
+        ];
+        let rp = ReadPropertyACK::parse(bytes).unwrap();
+        assert_eq!(rp.array_index, None);
+        let mut values = rp.values();
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(id.instance, 111);
+            }
+            _ => panic!("should be ObjectId"),
+        }
+        match values.next().unwrap().unwrap() {
+            ApplicationValue::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectAnalogInput);
+                assert_eq!(id.instance, 1);
+            }
+            _ => panic!("should be ObjectId"),
+        }
+        assert!(values.next().is_none());
+
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x3f,
+        ];
+        let rp = ReadPropertyACK::parse(bytes).unwrap();
+        assert_eq!(rp.property_id, PropertyId::PropPresentValue);
+        let mut values = rp.values();
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        assert!(values.next().is_none());
+
+        // a context tagged value cannot be decoded as an application value
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x19, 0x55, 0x3e, 0x09, 0x01, 0x3f,
+        ];
+        let rp = ReadPropertyACK::parse(bytes).unwrap();
+        let mut values = rp.values();
+        assert!(values.next().unwrap().is_err());
+        assert!(values.next().is_none());
+    }
+}
@@ -0,0 +1,303 @@
+use super::{
+    error::BACnetError,
+    tag::{parse_context_tagged, parse_next, parse_optional_context_tagged, Tag},
+    value::ApplicationValues,
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned, property_id::PropertyId};
+use crate::Error;
+
+/// Iterates over the read-access-specifications of a ReadPropertyMultiple service request.
+///
+/// Construct it from the service request bytes of a confirmed request, i.e.
+/// `ConfirmedRequestHeader::service_request()`.
+pub struct ReadAccessSpecifications<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ReadAccessSpecifications<'a> {
+    type Item = Result<ReadAccessSpecification<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, ReadAccessSpecification::parse)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ReadAccessSpecifications<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+/// An object and the properties of it to read.
+pub struct ReadAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    /// The encoded list of BACnetPropertyReference, excluding the enclosing tags.
+    pub list_of_property_references: &'a [u8],
+}
+
+impl<'a> ReadAccessSpecification<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, list_of_property_references) = Tag::parse_constructed(bytes, 1)?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                list_of_property_references,
+            },
+        ))
+    }
+
+    pub fn property_references(&self) -> PropertyReferences<'a> {
+        PropertyReferences {
+            bytes: self.list_of_property_references,
+        }
+    }
+}
+
+/// Iterates over the property references of a read-access-specification.
+pub struct PropertyReferences<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for PropertyReferences<'a> {
+    type Item = Result<PropertyReference, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, PropertyReference::parse)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PropertyReference {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl PropertyReference {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, property_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 1, parse_unsigned)?;
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+            },
+        ))
+    }
+}
+
+/// Iterates over the read-access-results of a ReadPropertyMultiple-ACK.
+///
+/// Construct it from the service ACK bytes of a complex ACK, i.e. `ComplexACKPDU::service_ack()`.
+pub struct ReadAccessResults<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ReadAccessResults<'a> {
+    type Item = Result<ReadAccessResult<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, ReadAccessResult::parse)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ReadAccessResults<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+/// An object and the results of reading its properties.
+pub struct ReadAccessResult<'a> {
+    pub object_id: ObjectId,
+    /// The encoded list of results, excluding the enclosing tags. Empty if the list is absent.
+    pub list_of_results: &'a [u8],
+}
+
+impl<'a> ReadAccessResult<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, list_of_results) = if Tag::is_opening(bytes, 1) {
+            Tag::parse_constructed(bytes, 1)?
+        } else {
+            (bytes, &[][..])
+        };
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                list_of_results,
+            },
+        ))
+    }
+
+    pub fn results(&self) -> ReadResults<'a> {
+        ReadResults {
+            bytes: self.list_of_results,
+        }
+    }
+}
+
+/// Iterates over the per-property results of a read-access-result.
+pub struct ReadResults<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ReadResults<'a> {
+    type Item = Result<ReadResult<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, ReadResult::parse)
+    }
+}
+
+/// The result of reading one property.
+pub struct ReadResult<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    pub result: PropertyResult<'a>,
+}
+
+impl<'a> ReadResult<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, property_id) = parse_context_tagged(bytes, 2, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 3, parse_unsigned)?;
+        let (bytes, result) = if Tag::is_opening(bytes, 4) {
+            let (bytes, value) = Tag::parse_constructed(bytes, 4)?;
+            (bytes, PropertyResult::Value(value))
+        } else {
+            let (bytes, error) = Tag::parse_constructed(bytes, 5)?;
+            let (_, error) = BACnetError::parse(error)?;
+            (bytes, PropertyResult::Error(error))
+        };
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+                result,
+            },
+        ))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyResult<'a> {
+    /// The encoded property value, excluding the enclosing tags.
+    Value(&'a [u8]),
+    /// The error returned for the property access.
+    Error(BACnetError),
+}
+
+impl<'a> PropertyResult<'a> {
+    /// Iterate over the property value as application tagged values. Returns `None` if the
+    /// property access resulted in an error.
+    pub fn values(&self) -> Option<ApplicationValues<'a>> {
+        match self {
+            Self::Value(value) => Some((*value).into()),
+            Self::Error(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::{
+        confirmed_request_pdu::ConfirmedRequestHeader,
+        error::{ErrorClass, ErrorCode},
+        value::ApplicationValue,
+        ComplexACKPDU, ConfirmedServiceChoice,
+    };
+    use crate::nsdu::{object_type::ObjectType, parse_apdu};
+
+    #[test]
+    fn read_property_multiple_request_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x02, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x09, 0x55, 0x09, 0x1c, 0x1f, // AI 1
+            0x0c, 0x02, 0x00, 0x00, 0x6f, 0x1e, 0x09, 0x4c, 0x19, 0x00, 0x1f, // device 111
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::ReadPropertyMultiple
+        );
+        let mut specs = ReadAccessSpecifications::from(header.service_request());
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectAnalogInput);
+        assert_eq!(spec.object_id.instance, 1);
+        let mut refs = spec.property_references();
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropPresentValue);
+        assert_eq!(r.array_index, None);
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropDescription);
+        assert!(refs.next().is_none());
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectDevice);
+        let mut refs = spec.property_references();
+        let r = refs.next().unwrap().unwrap();
+        assert_eq!(r.property_id, PropertyId::PropObjectList);
+        assert_eq!(r.array_index, Some(0));
+        assert!(refs.next().is_none());
+        assert!(specs.next().is_none());
+
+        // missing closing tag
+        let mut specs = ReadAccessSpecifications::from(&header.service_request()[..10]);
+        assert!(specs.next().unwrap().is_err());
+        assert!(specs.next().is_none());
+    }
+
+    #[test]
+    fn read_property_multiple_ack_test() {
+        let bytes: &[u8] = &[
+            0x30, 0x05, 0x0e, // header
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, // AI 1
+            0x29, 0x55, 0x4e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x4f, // present value
+            0x29, 0x1c, 0x5e, 0x91, 0x02, 0x91, 0x20, 0x5f, // description error
+            0x1f, //
+            0x0c, 0x02, 0x00, 0x00, 0x6f, 0x1e, // device 111
+            0x29, 0x4c, 0x39, 0x00, 0x4e, 0x21, 0x05, 0x4f, // object list length
+            0x1f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let ack = ComplexACKPDU::parse(&apdu).unwrap();
+        let mut results = ReadAccessResults::from(ack.service_ack());
+
+        let result = results.next().unwrap().unwrap();
+        assert_eq!(result.object_id.object_type, ObjectType::ObjectAnalogInput);
+        let mut reads = result.results();
+        let read = reads.next().unwrap().unwrap();
+        assert_eq!(read.property_id, PropertyId::PropPresentValue);
+        assert_eq!(read.array_index, None);
+        let mut values = read.result.values().unwrap();
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        assert!(values.next().is_none());
+        let read = reads.next().unwrap().unwrap();
+        assert_eq!(read.property_id, PropertyId::PropDescription);
+        match read.result {
+            PropertyResult::Error(e) => {
+                assert_eq!(e.error_class, ErrorClass::Property);
+                assert_eq!(e.error_code, ErrorCode::UnknownProperty);
+            }
+            _ => panic!("should be an error"),
+        }
+        assert!(read.result.values().is_none());
+        assert!(reads.next().is_none());
+
+        let result = results.next().unwrap().unwrap();
+        assert_eq!(result.object_id.instance, 111);
+        let mut reads = result.results();
+        let read = reads.next().unwrap().unwrap();
+        assert_eq!(read.property_id, PropertyId::PropObjectList);
+        assert_eq!(read.array_index, Some(0));
+        assert_eq!(read.result, PropertyResult::Value(&[0x21, 0x05]));
+        assert!(reads.next().is_none());
+        assert!(results.next().is_none());
+    }
+}
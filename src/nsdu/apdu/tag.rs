@@ -1,7 +1,8 @@
 use crate::Error;
 use arrayref::array_ref;
 
-enum TagType {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TagType {
     Null,
     Boolean,
     UnsignedInt,
@@ -45,8 +46,29 @@ impl From<u8> for TagType {
     }
 }
 
+/// The class of a tag, from clause 20.2.1.1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TagClass {
+    Application,
+    Context,
+}
+
+/// Whether a tag starts primitive data or delimits constructed data, from clause 20.2.1.3.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TagKind {
+    Primitive,
+    Opening,
+    Closing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Tag {
     pub number: u8,
+    pub class: TagClass,
+    pub kind: TagKind,
+    /// The length/value/type field. For primitive tags this is the length of the contents,
+    /// except for application tagged booleans where it is the boolean value. It is 0 for opening
+    /// and closing tags.
     pub value: u32,
 }
 
@@ -55,59 +77,299 @@ impl Tag {
     /// 0. Returns a tuple of the byte slice after the tag as well as the tag information.
     pub fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
         let (tag_bytes, number) = parse_tag_number(bytes)?;
-        if is_extended_value(bytes[0]) {
+        let class = if is_context_specific(bytes[0]) {
+            TagClass::Context
+        } else {
+            TagClass::Application
+        };
+        let kind = if class == TagClass::Context && is_opening_tag(bytes[0]) {
+            TagKind::Opening
+        } else if class == TagClass::Context && is_closing_tag(bytes[0]) {
+            TagKind::Closing
+        } else {
+            TagKind::Primitive
+        };
+        let tag = |value| Self {
+            number,
+            class,
+            kind,
+            value,
+        };
+        if kind != TagKind::Primitive {
+            Ok((tag_bytes, tag(0)))
+        } else if is_extended_value(bytes[0]) {
             if tag_bytes.is_empty() {
                 return Err(Error::Length("parsing tag"));
             }
-            match bytes[0] {
+            match tag_bytes[0] {
                 255 => {
-                    if tag_bytes.len() < 4 {
+                    if tag_bytes.len() < 5 {
                         return Err(Error::Length("parsing u32 tag"));
                     }
-                    let value = u32::from_be_bytes(*array_ref!(tag_bytes, 0, 4));
-                    Ok((&tag_bytes[4..], Self { number, value }))
+                    let value = u32::from_be_bytes(*array_ref!(tag_bytes, 1, 4));
+                    Ok((&tag_bytes[5..], tag(value)))
                 }
                 254 => {
-                    if tag_bytes.len() < 2 {
+                    if tag_bytes.len() < 3 {
                         return Err(Error::Length("parsing u16 tag"));
                     }
-                    let value = (u16::from_be_bytes(*array_ref!(tag_bytes, 0, 2))).into();
-                    Ok((&tag_bytes[2..], Self { number, value }))
+                    let value = (u16::from_be_bytes(*array_ref!(tag_bytes, 1, 2))).into();
+                    Ok((&tag_bytes[3..], tag(value)))
                 }
-                _value => Ok((
-                    &tag_bytes[1..],
-                    Self {
-                        number,
-                        value: tag_bytes[0].into(),
-                    },
-                )),
+                value => Ok((&tag_bytes[1..], tag(value.into()))),
             }
-        } else if is_opening_tag(bytes[0]) | is_closing_tag(bytes[0]) {
-            Ok((tag_bytes, Self { number, value: 0 }))
         } else {
-            let value = (bytes[0] & 0x07).into();
-            Ok((tag_bytes, Self { number, value }))
+            Ok((tag_bytes, tag((bytes[0] & 0x07).into())))
+        }
+    }
+
+    /// The number of content octets following the tag. Opening and closing tags as well as
+    /// application tagged booleans have no contents.
+    pub fn content_len(&self) -> u32 {
+        match (self.class, self.kind) {
+            (_, TagKind::Opening) | (_, TagKind::Closing) => 0,
+            (TagClass::Application, _) if self.tag_type() == TagType::Boolean => 0,
+            _ => self.value,
+        }
+    }
+
+    /// Parse a tag, checking that it is an application tag of the expected type.
+    pub fn parse_application(bytes: &[u8], expected: TagType) -> Result<(&[u8], Self), Error> {
+        let (rest, tag) = Self::parse(bytes)?;
+        if tag.class != TagClass::Application || tag.tag_type() != expected {
+            return Err(Error::InvalidValue("unexpected application tag"));
+        }
+        Ok((rest, tag))
+    }
+
+    /// Parse a tag, checking that it is a context specific tag with the expected tag number.
+    pub fn parse_context(bytes: &[u8], number: u8) -> Result<(&[u8], Self), Error> {
+        let (rest, tag) = Self::parse(bytes)?;
+        if tag.class != TagClass::Context || tag.kind != TagKind::Primitive || tag.number != number
+        {
+            return Err(Error::InvalidValue("unexpected context tag"));
         }
+        Ok((rest, tag))
+    }
+
+    /// Returns true if the bytes start with an application tag of the given type.
+    pub fn is_application(bytes: &[u8], expected: TagType) -> bool {
+        !bytes.is_empty() && Self::parse_application(bytes, expected).is_ok()
     }
 
+    /// Returns true if the bytes start with a context specific tag with the given tag number.
+    pub fn is_context(bytes: &[u8], number: u8) -> bool {
+        !bytes.is_empty() && Self::parse_context(bytes, number).is_ok()
+    }
+
+    /// Returns true if the bytes start with the opening tag of the given context tag number.
+    pub fn is_opening(bytes: &[u8], number: u8) -> bool {
+        !bytes.is_empty()
+            && is_context_specific(bytes[0])
+            && is_opening_tag(bytes[0])
+            && matches!(parse_tag_number(bytes), Ok((_, n)) if n == number)
+    }
+
+    /// Parse constructed data enclosed by the opening and closing tags of the given context tag
+    /// number. Returns a tuple of the byte slice after the closing tag as well as the enclosed
+    /// bytes.
+    pub fn parse_constructed(bytes: &[u8], number: u8) -> Result<(&[u8], &[u8]), Error> {
+        if !Self::is_opening(bytes, number) {
+            return Err(Error::InvalidValue("expected opening tag"));
+        }
+        let (contents, _) = parse_tag_number(bytes)?;
+        let mut walker = TagWalker::from(bytes);
+        loop {
+            let rest = walker.bytes;
+            match walker.next() {
+                Some(Ok(TagEvent::Close(_))) if walker.depth() == 0 => {
+                    let enclosed = &contents[..contents.len() - rest.len()];
+                    return Ok((walker.bytes, enclosed));
+                }
+                Some(Ok(_)) => (),
+                Some(Err(e)) => return Err(e),
+                None => return Err(Error::Length("missing closing tag")),
+            }
+        }
+    }
+
+    /// The application datatype of the tag. Only meaningful for application tags.
     pub fn tag_type(&self) -> TagType {
         self.number.into()
     }
 }
 
+/// Parse an application tag of the expected type, then decode its contents with `f` which is
+/// given the bytes after the tag and the tag's value.
+pub(crate) fn parse_application_tagged<'a, T, F>(
+    bytes: &'a [u8],
+    expected: TagType,
+    f: F,
+) -> Result<(&'a [u8], T), Error>
+where
+    F: FnOnce(&'a [u8], u32) -> Result<(&'a [u8], T), Error>,
+{
+    let (bytes, tag) = Tag::parse_application(bytes, expected)?;
+    f(bytes, tag.value)
+}
+
+/// Parse a context specific tag with the given tag number, then decode its contents with `f`
+/// which is given the bytes after the tag and the tag's value.
+pub(crate) fn parse_context_tagged<'a, T, F>(
+    bytes: &'a [u8],
+    number: u8,
+    f: F,
+) -> Result<(&'a [u8], T), Error>
+where
+    F: FnOnce(&'a [u8], u32) -> Result<(&'a [u8], T), Error>,
+{
+    let (bytes, tag) = Tag::parse_context(bytes, number)?;
+    f(bytes, tag.value)
+}
+
+/// Same as `parse_context_tagged` for an optional parameter. Returns `None` without consuming
+/// any bytes if the next tag is not the given context tag.
+pub(crate) fn parse_optional_context_tagged<'a, T, F>(
+    bytes: &'a [u8],
+    number: u8,
+    f: F,
+) -> Result<(&'a [u8], Option<T>), Error>
+where
+    F: FnOnce(&'a [u8], u32) -> Result<(&'a [u8], T), Error>,
+{
+    if Tag::is_context(bytes, number) {
+        let (bytes, value) = parse_context_tagged(bytes, number, f)?;
+        Ok((bytes, Some(value)))
+    } else {
+        Ok((bytes, None))
+    }
+}
+
+/// An event produced by `TagWalker`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TagEvent<'a> {
+    /// An opening tag with the given context tag number.
+    Open(u8),
+    /// A primitive tag along with its content octets.
+    Primitive(Tag, &'a [u8]),
+    /// A closing tag with the given context tag number.
+    Close(u8),
+}
+
+/// The maximum depth of nested constructed data supported by `TagWalker`.
+pub const MAX_TAG_DEPTH: usize = 16;
+
+/// Walks a sequence of tags, yielding an event for each opening, primitive and closing tag.
+///
+/// Nesting is validated: every closing tag must match the most recent unclosed opening tag and
+/// all opening tags must be closed by the end of the bytes. An error is returned otherwise, or if
+/// nesting is deeper than `MAX_TAG_DEPTH`, after which iteration stops.
+pub struct TagWalker<'a> {
+    bytes: &'a [u8],
+    open: [u8; MAX_TAG_DEPTH],
+    depth: usize,
+}
+
+impl<'a> TagWalker<'a> {
+    /// The number of currently unclosed opening tags.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn parse_event(&mut self, bytes: &'a [u8]) -> Result<(&'a [u8], TagEvent<'a>), Error> {
+        let (bytes, tag) = Tag::parse(bytes)?;
+        match tag.kind {
+            TagKind::Opening => {
+                if self.depth == MAX_TAG_DEPTH {
+                    return Err(Error::InvalidValue("tags nested too deeply"));
+                }
+                self.open[self.depth] = tag.number;
+                self.depth += 1;
+                Ok((bytes, TagEvent::Open(tag.number)))
+            }
+            TagKind::Closing => {
+                if self.depth == 0 {
+                    return Err(Error::InvalidValue("closing tag without opening tag"));
+                }
+                if self.open[self.depth - 1] != tag.number {
+                    return Err(Error::InvalidValue("mismatched closing tag"));
+                }
+                self.depth -= 1;
+                Ok((bytes, TagEvent::Close(tag.number)))
+            }
+            TagKind::Primitive => {
+                let len = tag.content_len() as usize;
+                if bytes.len() < len {
+                    return Err(Error::Length("tag len greater than remaining bytes"));
+                }
+                Ok((&bytes[len..], TagEvent::Primitive(tag, &bytes[..len])))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for TagWalker<'a> {
+    type Item = Result<TagEvent<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            if self.depth == 0 {
+                return None;
+            }
+            self.depth = 0;
+            return Some(Err(Error::Length("missing closing tag")));
+        }
+        let mut bytes = self.bytes;
+        let event = parse_next(&mut bytes, |bytes| self.parse_event(bytes));
+        self.bytes = bytes;
+        if let Some(Err(_)) = event {
+            self.depth = 0;
+        }
+        event
+    }
+}
+
+impl<'a> From<&'a [u8]> for TagWalker<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            open: [0; MAX_TAG_DEPTH],
+            depth: 0,
+        }
+    }
+}
+
+/// Parse the next element of an encoded sequence with `f`, advancing `bytes` past it. Returns
+/// `None` once `bytes` is empty. On error `bytes` is emptied so that iteration stops.
+pub(crate) fn parse_next<'a, T, F>(bytes: &mut &'a [u8], f: F) -> Option<Result<T, Error>>
+where
+    F: FnOnce(&'a [u8]) -> Result<(&'a [u8], T), Error>,
+{
+    if bytes.is_empty() {
+        return None;
+    }
+    match f(bytes) {
+        Ok((rest, value)) => {
+            *bytes = rest;
+            Some(Ok(value))
+        }
+        Err(e) => {
+            *bytes = &[];
+            Some(Err(e))
+        }
+    }
+}
+
 fn parse_tag_number(bytes: &[u8]) -> Result<(&[u8], u8), Error> {
-    if is_extended_tag_number(bytes[0]) {
+    if bytes.is_empty() {
+        Err(Error::Length("cannot read tag"))
+    } else if is_extended_tag_number(bytes[0]) {
         if bytes.len() < 2 {
             Err(Error::Length("cannot read tag"))
         } else {
             Ok((&bytes[2..], bytes[1]))
         }
     } else {
-        if bytes.is_empty() {
-            Err(Error::Length("cannot read tag"))
-        } else {
-            Ok((&bytes[1..], bytes[0] >> 4))
-        }
+        Ok((&bytes[1..], bytes[0] >> 4))
     }
 }
 
@@ -134,3 +396,144 @@ fn is_opening_tag(tagnum: u8) -> bool {
 fn is_closing_tag(tagnum: u8) -> bool {
     tagnum & 0x07 == 7
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_class_kind_test() {
+        let (_, tag) = Tag::parse(&[0x3e]).unwrap();
+        assert_eq!(tag.number, 3);
+        assert_eq!(tag.class, TagClass::Context);
+        assert_eq!(tag.kind, TagKind::Opening);
+        assert_eq!(tag.content_len(), 0);
+
+        let (_, tag) = Tag::parse(&[0x3f]).unwrap();
+        assert_eq!(tag.kind, TagKind::Closing);
+
+        let (rest, tag) = Tag::parse(&[0x39, 0x00]).unwrap();
+        assert_eq!(tag.number, 3);
+        assert_eq!(tag.class, TagClass::Context);
+        assert_eq!(tag.kind, TagKind::Primitive);
+        assert_eq!(tag.value, 1);
+        assert_eq!(rest, &[0x00]);
+
+        // application tags with a length of 6 or 7 are not opening or closing tags
+        let (_, tag) = Tag::parse(&[0x66]).unwrap();
+        assert_eq!(tag.class, TagClass::Application);
+        assert_eq!(tag.kind, TagKind::Primitive);
+        assert_eq!(tag.tag_type(), TagType::OctetString);
+        assert_eq!(tag.content_len(), 6);
+
+        let (_, tag) = Tag::parse(&[0x11]).unwrap();
+        assert_eq!(tag.tag_type(), TagType::Boolean);
+        assert_eq!(tag.value, 1);
+        assert_eq!(tag.content_len(), 0);
+
+        let (_, tag) = Tag::parse(&[0xfe, 0x20]).unwrap();
+        assert_eq!(tag.number, 0x20);
+        assert_eq!(tag.kind, TagKind::Opening);
+    }
+
+    #[test]
+    fn tag_walker_test() {
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x00, 0x00, 0x01, 0x1e, 0x29, 0x55, 0x4e, 0x44, 0x42, 0x90, 0x00, 0x00,
+            0x4f, 0x1f,
+        ];
+        let mut walker = TagWalker::from(bytes);
+        match walker.next().unwrap().unwrap() {
+            TagEvent::Primitive(tag, content) => {
+                assert_eq!(tag.number, 0);
+                assert_eq!(tag.class, TagClass::Context);
+                assert_eq!(content, &[0x00, 0x00, 0x00, 0x01]);
+            }
+            _ => panic!("should be primitive"),
+        }
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Open(1));
+        assert_eq!(walker.depth(), 1);
+        match walker.next().unwrap().unwrap() {
+            TagEvent::Primitive(tag, content) => {
+                assert_eq!(tag.number, 2);
+                assert_eq!(content, &[0x55]);
+            }
+            _ => panic!("should be primitive"),
+        }
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Open(4));
+        assert_eq!(walker.depth(), 2);
+        match walker.next().unwrap().unwrap() {
+            TagEvent::Primitive(tag, content) => {
+                assert_eq!(tag.class, TagClass::Application);
+                assert_eq!(tag.tag_type(), TagType::Real);
+                assert_eq!(content, &[0x42, 0x90, 0x00, 0x00]);
+            }
+            _ => panic!("should be primitive"),
+        }
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Close(4));
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Close(1));
+        assert_eq!(walker.depth(), 0);
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn tag_walker_nesting_errors_test() {
+        // mismatched closing tag
+        let mut walker = TagWalker::from(&[0x1e, 0x2f][..]);
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Open(1));
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+
+        // closing tag without opening tag
+        let mut walker = TagWalker::from(&[0x1f][..]);
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+
+        // missing closing tag
+        let mut walker = TagWalker::from(&[0x1e, 0x09, 0x00][..]);
+        assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Open(1));
+        assert!(walker.next().unwrap().is_ok());
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+
+        // contents longer than the remaining bytes
+        let mut walker = TagWalker::from(&[0x0a, 0x00][..]);
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+
+        // nested too deeply
+        let bytes = [0x0e; MAX_TAG_DEPTH + 1];
+        let mut walker = TagWalker::from(&bytes[..]);
+        for _ in 0..MAX_TAG_DEPTH {
+            assert_eq!(walker.next().unwrap().unwrap(), TagEvent::Open(0));
+        }
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn parse_constructed_test() {
+        // [4]{ 9:0x05 [1]{ } } followed by a trailing byte
+        let bytes = [0x4e, 0x09, 0x05, 0x1e, 0x1f, 0x4f, 0xff];
+        let (rest, enclosed) = Tag::parse_constructed(&bytes, 4).unwrap();
+        assert_eq!(enclosed, &[0x09, 0x05, 0x1e, 0x1f]);
+        assert_eq!(rest, &[0xff]);
+
+        // wrong opening tag number
+        assert!(Tag::parse_constructed(&bytes, 3).is_err());
+        // mismatched outer closing tag
+        assert!(Tag::parse_constructed(&[0x4e, 0x3f], 4).is_err());
+        // mismatched inner closing tag
+        assert!(Tag::parse_constructed(&[0x4e, 0x1e, 0x2f, 0x4f], 4).is_err());
+        // missing closing tag
+        assert!(Tag::parse_constructed(&[0x4e, 0x1e, 0x1f], 4).is_err());
+
+        // nested too deeply
+        let mut bytes = [0x0e; 2 * MAX_TAG_DEPTH + 2];
+        for b in bytes[MAX_TAG_DEPTH + 1..].iter_mut() {
+            *b = 0x0f;
+        }
+        assert!(Tag::parse_constructed(&bytes, 0).is_err());
+        assert!(Tag::parse_constructed(&bytes[1..bytes.len() - 1], 0).is_ok());
+    }
+}
@@ -1,27 +1,60 @@
-use super::{tag::Tag, APDU};
-use crate::nsdu::parse_unsigned;
+use super::{
+    cov::{COVNotification, COVNotificationMultiple},
+    event::EventNotification,
+    tag::{
+        parse_application_tagged, parse_context_tagged, parse_optional_context_tagged, Tag, TagType,
+    },
+    value::{parse_boolean, parse_octet_string, CharacterString, DateTime},
+    Segmentation, APDU,
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned, parse_unsigned16, parse_unsigned8};
 use crate::Error;
 
-pub enum UnconfirmedServiceChoice {
-    IAm, // src/iam.c:77
-    IHave,
-    WhoHas,
+pub enum UnconfirmedServiceChoice<'a> {
+    IAm(IAmData),         // src/iam.c:77
+    IHave(IHaveData<'a>), // src/ihave.c
+    UnconfirmedCOVNotification(COVNotification<'a>),
+    UnconfirmedEventNotification(EventNotification<'a>),
+    UnconfirmedPrivateTransfer(PrivateTransferData<'a>),
+    UnconfirmedTextMessage(TextMessageData<'a>),
+    TimeSynchronization(DateTime),
+    WhoHas(WhoHasData<'a>),     // src/whohas.c
     WhoIs(Option<WhoIsLimits>), // src/whois.c:69
-    Unknown,
+    UTCTimeSynchronization(DateTime),
+    WriteGroup(WriteGroupData<'a>),
+    UnconfirmedCOVNotificationMultiple(COVNotificationMultiple<'a>),
+    /// The service request bytes of an UnconfirmedAuditNotification, which are not decoded.
+    UnconfirmedAuditNotification(&'a [u8]),
+    WhoAmI(WhoAmIData<'a>),
+    YouAre(YouAreData<'a>),
+    Unknown(u8),
 }
 
-impl UnconfirmedServiceChoice {
-    pub fn parse(apdu: &APDU) -> Result<Self, Error> {
+impl<'a> UnconfirmedServiceChoice<'a> {
+    pub fn parse(apdu: &APDU<'a>) -> Result<Self, Error> {
         let bytes = apdu.bytes;
         if bytes.len() < 2 {
             return Err(Error::Length("wrong len for UnconfirmedServiceChoice"));
         }
         Ok(match bytes[1] {
-            0x00 => Self::IAm,
-            0x01 => Self::IHave,
-            0x07 => Self::WhoHas,
-            0x08 => Self::WhoIs(WhoIsLimits::parse(apdu)?),
-            _ => Self::Unknown,
+            0x00 => Self::IAm(IAmData::parse(&bytes[2..])?),
+            0x01 => Self::IHave(IHaveData::parse(&bytes[2..])?),
+            0x02 => Self::UnconfirmedCOVNotification(COVNotification::parse(&bytes[2..])?),
+            0x03 => Self::UnconfirmedEventNotification(EventNotification::parse(&bytes[2..])?),
+            0x04 => Self::UnconfirmedPrivateTransfer(PrivateTransferData::parse(&bytes[2..])?),
+            0x05 => Self::UnconfirmedTextMessage(TextMessageData::parse(&bytes[2..])?),
+            0x06 => Self::TimeSynchronization(DateTime::parse(&bytes[2..])?.1),
+            0x07 => Self::WhoHas(WhoHasData::parse(&bytes[2..])?),
+            0x08 => Self::WhoIs(WhoIsLimits::parse(&bytes[2..])?),
+            0x09 => Self::UTCTimeSynchronization(DateTime::parse(&bytes[2..])?.1),
+            0x0a => Self::WriteGroup(WriteGroupData::parse(&bytes[2..])?),
+            0x0b => Self::UnconfirmedCOVNotificationMultiple(COVNotificationMultiple::parse(
+                &bytes[2..],
+            )?),
+            0x0c => Self::UnconfirmedAuditNotification(&bytes[2..]),
+            0x0d => Self::WhoAmI(WhoAmIData::parse(&bytes[2..])?),
+            0x0e => Self::YouAre(YouAreData::parse(&bytes[2..])?),
+            b => Self::Unknown(b),
         })
     }
 }
@@ -32,57 +65,294 @@ pub struct WhoIsLimits {
 }
 
 impl WhoIsLimits {
-    /// Attempt to parse WhoIsLimits from an APDU payload.
-    fn parse(apdu: &APDU) -> Result<Option<Self>, Error> {
-        match apdu.bytes.len() {
-            // Safety:
-            // This must called from UnconfirmedServiceChoice which validates that this must be an
-            // APDU frame with at least 2 payload bytes available.
-            0 | 1 => unsafe { core::hint::unreachable_unchecked() },
-            2 => Ok(None),
-            _ => {
-                // 1. parse a tag, starting from after the pdu type and service choice
-                // 2. parse an unsigned value. The tag's value here is the length of the unsigned
-                //    integer. This is the low value.
-                // 3. parse another tag
-                // 4. parse another unsigned value. This is the high value.
-                let (bytes, tag) = Tag::parse(&apdu.bytes[2..])?;
-                if tag.number != 0 {
-                    return Err(Error::InvalidValue("Non-zero tag number in WhoIs"));
-                }
-                let (bytes, low_limit) = parse_unsigned(bytes, tag.value)?;
-                let (bytes, tag) = Tag::parse(bytes)?;
-                let (_, high_limit) = parse_unsigned(bytes, tag.value)?;
-                Ok(Some(Self {
-                    low_limit,
-                    high_limit,
-                }))
-            }
+    /// Attempt to parse WhoIsLimits from the service request bytes. The limits are optional so
+    /// no bytes gives `None`.
+    fn parse(bytes: &[u8]) -> Result<Option<Self>, Error> {
+        if bytes.is_empty() {
+            return Ok(None);
         }
+        let (bytes, low_limit) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (_, high_limit) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        Ok(Some(Self {
+            low_limit,
+            high_limit,
+        }))
     }
 }
 
-pub struct IAmData {}
+pub struct IAmData {
+    pub device_id: ObjectId,
+    pub max_apdu: u32,
+    pub segmentation: Segmentation,
+    pub vendor_id: u16,
+}
 
 impl IAmData {
-    /// Attempt to parse WhoIsLimits from an APDU payload.
-    fn parse(apdu: &APDU) -> Result<Option<Self>, Error> {
-        match apdu.bytes.len() {
-            // Safety:
-            // This must called from UnconfirmedServiceChoice which validates that this must be an
-            // APDU frame with at least 2 payload bytes available.
-            0 | 1 => unsafe { core::hint::unreachable_unchecked() },
-            _ => {
-                // 1. parse a tag, type should be ObjectId
-                // 2. decode an object ID - this is the device id
-                // 3. parse a tag, type should be UnsignedInt
-                // 4. decode an unsigned int - this is "Max APDU" - TODO: what does that mean?
-                // 5. parse a tag, type should be enumerated
-                // 6. decode an enumerated value - this is segmentation support
-                // 7. parse a tag, type should be UnsignedInt
-                // 8. decode an enumerated value - this is the vendor ID
-                unimplemented!("TODO");
-            }
+    /// Attempt to parse IAmData from the service request bytes, i.e. the APDU payload after the
+    /// pdu type and service choice.
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        // 1. parse a tag, type should be ObjectId
+        // 2. decode an object ID - this is the device id
+        // 3. parse a tag, type should be UnsignedInt
+        // 4. decode an unsigned int - this is the max APDU length accepted by the device
+        // 5. parse a tag, type should be enumerated
+        // 6. decode an enumerated value - this is segmentation support
+        // 7. parse a tag, type should be UnsignedInt
+        // 8. decode an unsigned int - this is the vendor ID
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::ObjectId)?;
+        let (bytes, device_id) = ObjectId::parse(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::UnsignedInt)?;
+        let (bytes, max_apdu) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::Enumerated)?;
+        let (bytes, segmentation) = parse_unsigned(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::UnsignedInt)?;
+        let (_, vendor_id) = parse_unsigned16(bytes, tag.value)?;
+        Ok(Self {
+            device_id,
+            max_apdu,
+            segmentation: segmentation.into(),
+            vendor_id,
+        })
+    }
+}
+
+pub struct IHaveData<'a> {
+    pub device_id: ObjectId,
+    pub object_id: ObjectId,
+    pub object_name: CharacterString<'a>,
+}
+
+impl<'a> IHaveData<'a> {
+    /// Attempt to parse IHaveData from the service request bytes, i.e. the APDU payload after the
+    /// pdu type and service choice.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        // 1. parse a tag, type should be ObjectId
+        // 2. decode an object ID - this is the device id
+        // 3. parse a tag, type should be ObjectId
+        // 4. decode an object ID - this is the id of the object that was found
+        // 5. parse a tag, type should be CharacterString
+        // 6. decode a character string - this is the name of the object that was found
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::ObjectId)?;
+        let (bytes, device_id) = ObjectId::parse(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::ObjectId)?;
+        let (bytes, object_id) = ObjectId::parse(bytes, tag.value)?;
+        let (bytes, tag) = Tag::parse_application(bytes, TagType::CharacterString)?;
+        let (_, object_name) = CharacterString::parse(bytes, tag.value)?;
+        Ok(Self {
+            device_id,
+            object_id,
+            object_name,
+        })
+    }
+}
+
+pub struct WhoHasData<'a> {
+    pub limits: Option<WhoIsLimits>,
+    pub object: WhoHasObject<'a>,
+}
+
+/// The object being searched for by a Who-Has, either by its id or by its name.
+pub enum WhoHasObject<'a> {
+    Id(ObjectId),
+    Name(CharacterString<'a>),
+}
+
+impl<'a> WhoHasData<'a> {
+    /// Attempt to parse WhoHasData from the service request bytes, i.e. the APDU payload after
+    /// the pdu type and service choice.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        // 1. parse a tag. If it is context tag 0 then the device instance range limits are
+        //    present: decode the low limit, then parse context tag 1 and decode the high limit,
+        //    then parse the next tag.
+        // 2. if the tag is context tag 2, decode an object ID
+        // 3. if the tag is context tag 3, decode a character string
+        let (bytes, limits) = if Tag::is_context(bytes, 0) {
+            let (bytes, tag) = Tag::parse_context(bytes, 0)?;
+            let (bytes, low_limit) = parse_unsigned(bytes, tag.value)?;
+            let (bytes, tag) = Tag::parse_context(bytes, 1)?;
+            let (bytes, high_limit) = parse_unsigned(bytes, tag.value)?;
+            (
+                bytes,
+                Some(WhoIsLimits {
+                    low_limit,
+                    high_limit,
+                }),
+            )
+        } else {
+            (bytes, None)
+        };
+        let object = if Tag::is_context(bytes, 2) {
+            let (bytes, tag) = Tag::parse_context(bytes, 2)?;
+            WhoHasObject::Id(ObjectId::parse(bytes, tag.value)?.1)
+        } else {
+            let (bytes, tag) = Tag::parse_context(bytes, 3)?;
+            WhoHasObject::Name(CharacterString::parse(bytes, tag.value)?.1)
+        };
+        Ok(Self { limits, object })
+    }
+}
+
+pub struct PrivateTransferData<'a> {
+    pub vendor_id: u16,
+    pub service_number: u32,
+    /// The encoded service parameters, excluding the enclosing tags.
+    pub service_parameters: Option<&'a [u8]>,
+}
+
+impl<'a> PrivateTransferData<'a> {
+    /// Attempt to parse PrivateTransferData from the service request bytes.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, vendor_id) = parse_context_tagged(bytes, 0, parse_unsigned16)?;
+        let (bytes, service_number) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        let service_parameters = if Tag::is_opening(bytes, 2) {
+            Some(Tag::parse_constructed(bytes, 2)?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            vendor_id,
+            service_number,
+            service_parameters,
+        })
+    }
+}
+
+pub struct TextMessageData<'a> {
+    pub source_device: ObjectId,
+    pub message_class: Option<MessageClass<'a>>,
+    pub message_priority: MessagePriority,
+    pub message: CharacterString<'a>,
+}
+
+pub enum MessageClass<'a> {
+    Numeric(u32),
+    Character(CharacterString<'a>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessagePriority {
+    Normal,
+    Urgent,
+    Unknown(u32),
+}
+
+impl From<u32> for MessagePriority {
+    fn from(b: u32) -> Self {
+        match b {
+            0 => Self::Normal,
+            1 => Self::Urgent,
+            b => Self::Unknown(b),
         }
     }
 }
+
+impl<'a> TextMessageData<'a> {
+    /// Attempt to parse TextMessageData from the service request bytes.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, source_device) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, message_class) = if Tag::is_opening(bytes, 1) {
+            let (rest, class) = Tag::parse_constructed(bytes, 1)?;
+            let class = if Tag::is_context(class, 0) {
+                MessageClass::Numeric(parse_context_tagged(class, 0, parse_unsigned)?.1)
+            } else {
+                MessageClass::Character(parse_context_tagged(class, 1, CharacterString::parse)?.1)
+            };
+            (rest, Some(class))
+        } else {
+            (bytes, None)
+        };
+        let (bytes, message_priority) = parse_context_tagged(bytes, 2, parse_unsigned)?;
+        let (_, message) = parse_context_tagged(bytes, 3, CharacterString::parse)?;
+        Ok(Self {
+            source_device,
+            message_class,
+            message_priority: message_priority.into(),
+            message,
+        })
+    }
+}
+
+pub struct WriteGroupData<'a> {
+    pub group_number: u32,
+    pub write_priority: u8,
+    /// The encoded list of BACnetGroupChannelValue, excluding the enclosing tags.
+    pub change_list: &'a [u8],
+    pub inhibit_write: Option<bool>,
+}
+
+impl<'a> WriteGroupData<'a> {
+    /// Attempt to parse WriteGroupData from the service request bytes.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, group_number) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, write_priority) = parse_context_tagged(bytes, 1, parse_unsigned8)?;
+        let (bytes, change_list) = Tag::parse_constructed(bytes, 2)?;
+        let (_, inhibit_write) = parse_optional_context_tagged(bytes, 3, parse_boolean)?;
+        Ok(Self {
+            group_number,
+            write_priority,
+            change_list,
+            inhibit_write,
+        })
+    }
+}
+
+pub struct WhoAmIData<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+}
+
+impl<'a> WhoAmIData<'a> {
+    /// Attempt to parse WhoAmIData from the service request bytes.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, vendor_id) =
+            parse_application_tagged(bytes, TagType::UnsignedInt, parse_unsigned16)?;
+        let (bytes, model_name) =
+            parse_application_tagged(bytes, TagType::CharacterString, CharacterString::parse)?;
+        let (_, serial_number) =
+            parse_application_tagged(bytes, TagType::CharacterString, CharacterString::parse)?;
+        Ok(Self {
+            vendor_id,
+            model_name,
+            serial_number,
+        })
+    }
+}
+
+pub struct YouAreData<'a> {
+    pub vendor_id: u16,
+    pub model_name: CharacterString<'a>,
+    pub serial_number: CharacterString<'a>,
+    pub device_id: Option<ObjectId>,
+    pub device_mac_address: Option<&'a [u8]>,
+}
+
+impl<'a> YouAreData<'a> {
+    /// Attempt to parse YouAreData from the service request bytes.
+    fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, vendor_id) =
+            parse_application_tagged(bytes, TagType::UnsignedInt, parse_unsigned16)?;
+        let (bytes, model_name) =
+            parse_application_tagged(bytes, TagType::CharacterString, CharacterString::parse)?;
+        let (bytes, serial_number) =
+            parse_application_tagged(bytes, TagType::CharacterString, CharacterString::parse)?;
+        let (bytes, device_id) = if Tag::is_application(bytes, TagType::ObjectId) {
+            let (rest, id) = parse_application_tagged(bytes, TagType::ObjectId, ObjectId::parse)?;
+            (rest, Some(id))
+        } else {
+            (bytes, None)
+        };
+        let device_mac_address = if Tag::is_application(bytes, TagType::OctetString) {
+            Some(parse_application_tagged(bytes, TagType::OctetString, parse_octet_string)?.1)
+        } else {
+            None
+        };
+        Ok(Self {
+            vendor_id,
+            model_name,
+            serial_number,
+            device_id,
+            device_mac_address,
+        })
+    }
+}
@@ -0,0 +1,701 @@
+use super::tag::{
+    parse_application_tagged, parse_context_tagged, parse_next, parse_optional_context_tagged, Tag,
+    TagClass, TagType,
+};
+use crate::nsdu::{
+    object_type::ObjectId, parse_signed, parse_unsigned, parse_unsigned64, parse_unsigned8,
+    property_id::PropertyId,
+};
+use crate::Error;
+use arrayref::array_ref;
+
+/// A primitive value encoded with an application tag.
+///
+/// Unsigned and Signed values of up to 8 octets are supported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApplicationValue<'a> {
+    Null,
+    Boolean(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Real(f32),
+    Double(f64),
+    OctetString(&'a [u8]),
+    CharacterString(CharacterString<'a>),
+    BitString(BitString<'a>),
+    Enumerated(u32),
+    Date(Date),
+    Time(Time),
+    ObjectId(ObjectId),
+}
+
+impl<'a> ApplicationValue<'a> {
+    /// Parse one application tagged value from the start of the bytes. Returns a tuple of the
+    /// byte slice after the value as well as the value.
+    ///
+    /// Context tagged values, opening and closing tags and the reserved application tags 13-15
+    /// are rejected with `Error::InvalidValue`.
+    ///
+    /// ```
+    /// # use bacnet_parse::Error;
+    /// # use bacnet_parse::nsdu::apdu::value::ApplicationValue;
+    /// # fn main() -> Result<(), Error> {
+    /// let bytes: &[u8] = &[0x21, 0x05, 0x91, 0x01];
+    /// let (bytes, value) = ApplicationValue::parse(bytes)?;
+    /// assert_eq!(value, ApplicationValue::Unsigned(5));
+    /// let (bytes, value) = ApplicationValue::parse(bytes)?;
+    /// assert_eq!(value, ApplicationValue::Enumerated(1));
+    /// assert!(bytes.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        if bytes.is_empty() {
+            return Err(Error::Length("no application value bytes"));
+        }
+        let (bytes, tag) = Tag::parse(bytes)?;
+        if tag.class != TagClass::Application {
+            return Err(Error::InvalidValue("expected application tag"));
+        }
+        Ok(match tag.tag_type() {
+            TagType::Null => {
+                if tag.value != 0 {
+                    return Err(Error::InvalidValue("null with non-zero length"));
+                }
+                (bytes, Self::Null)
+            }
+            TagType::Boolean => (bytes, Self::Boolean(tag.value != 0)),
+            TagType::UnsignedInt => {
+                let (bytes, v) = parse_unsigned64(bytes, tag.value)?;
+                (bytes, Self::Unsigned(v))
+            }
+            TagType::SignedInt => {
+                let (bytes, v) = parse_signed(bytes, tag.value)?;
+                (bytes, Self::Signed(v))
+            }
+            TagType::Real => {
+                let (bytes, v) = parse_real(bytes, tag.value)?;
+                (bytes, Self::Real(v))
+            }
+            TagType::Double => {
+                if tag.value != 8 || bytes.len() < 8 {
+                    return Err(Error::Length("wrong len for double"));
+                }
+                let v = f64::from_be_bytes(*array_ref!(bytes, 0, 8));
+                (&bytes[8..], Self::Double(v))
+            }
+            TagType::OctetString => {
+                let (bytes, v) = parse_octet_string(bytes, tag.value)?;
+                (bytes, Self::OctetString(v))
+            }
+            TagType::CharacterString => {
+                let (bytes, v) = CharacterString::parse(bytes, tag.value)?;
+                (bytes, Self::CharacterString(v))
+            }
+            TagType::BitString => {
+                let (bytes, v) = BitString::parse(bytes, tag.value)?;
+                (bytes, Self::BitString(v))
+            }
+            TagType::Enumerated => {
+                let (bytes, v) = parse_unsigned(bytes, tag.value)?;
+                (bytes, Self::Enumerated(v))
+            }
+            TagType::Date => {
+                let (bytes, v) = Date::parse(bytes, tag.value)?;
+                (bytes, Self::Date(v))
+            }
+            TagType::Time => {
+                let (bytes, v) = Time::parse(bytes, tag.value)?;
+                (bytes, Self::Time(v))
+            }
+            TagType::ObjectId => {
+                let (bytes, v) = ObjectId::parse(bytes, tag.value)?;
+                (bytes, Self::ObjectId(v))
+            }
+            _ => return Err(Error::InvalidValue("reserved application tag")),
+        })
+    }
+}
+
+/// Parse the contents of a REAL.
+pub(crate) fn parse_real(bytes: &[u8], len: u32) -> Result<(&[u8], f32), Error> {
+    if len != 4 || bytes.len() < 4 {
+        return Err(Error::Length("wrong len for real"));
+    }
+    Ok((&bytes[4..], f32::from_be_bytes(*array_ref!(bytes, 0, 4))))
+}
+
+/// Iterates over a sequence of application tagged values.
+///
+/// An error is returned if a value cannot be decoded, e.g. a context tagged or constructed value,
+/// after which iteration stops.
+pub struct ApplicationValues<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ApplicationValues<'a> {
+    type Item = Result<ApplicationValue<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, ApplicationValue::parse)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ApplicationValues<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+/// A bit string borrowed from the APDU bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BitString<'a> {
+    unused_bits: u8,
+    bytes: &'a [u8],
+}
+
+impl<'a> BitString<'a> {
+    /// Parse the contents of a bit string. `len` is the length taken from the tag and includes
+    /// the octet giving the number of unused bits.
+    pub(crate) fn parse(bytes: &'a [u8], len: u32) -> Result<(&'a [u8], Self), Error> {
+        let len = len as usize;
+        if len == 0 {
+            return Err(Error::InvalidValue("bit string has no unused bits octet"));
+        }
+        if bytes.len() < len {
+            return Err(Error::Length("bit string len greater than remaining bytes"));
+        }
+        if bytes[0] > 7 || (len == 1 && bytes[0] != 0) {
+            return Err(Error::InvalidValue("invalid bit string unused bits"));
+        }
+        Ok((
+            &bytes[len..],
+            Self {
+                unused_bits: bytes[0],
+                bytes: &bytes[1..len],
+            },
+        ))
+    }
+
+    /// The number of bits in the bit string.
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8 - self.unused_bits as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bit at the given index, where bit 0 is the most significant bit of the first
+    /// octet, or `None` if the index is out of range.
+    pub fn bit(&self, idx: usize) -> Option<bool> {
+        if idx >= self.len() {
+            return None;
+        }
+        Some(self.bytes[idx / 8] & (0x80 >> (idx % 8)) != 0)
+    }
+}
+
+/// The character set of a BACnet character string, given by its first content octet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharacterSet {
+    /// ISO 10646 (UTF-8). Formerly ANSI X3.4.
+    Utf8,
+    IbmMicrosoftDbcs,
+    JisX0208,
+    Ucs4,
+    Ucs2,
+    Iso8859_1,
+    Unknown(u8),
+}
+
+impl From<u8> for CharacterSet {
+    fn from(b: u8) -> Self {
+        match b {
+            0 => Self::Utf8,
+            1 => Self::IbmMicrosoftDbcs,
+            2 => Self::JisX0208,
+            3 => Self::Ucs4,
+            4 => Self::Ucs2,
+            5 => Self::Iso8859_1,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+/// A character string borrowed from the APDU bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CharacterString<'a> {
+    charset: CharacterSet,
+    bytes: &'a [u8],
+}
+
+impl<'a> CharacterString<'a> {
+    /// Parse the contents of a character string. `len` is the length taken from the tag and
+    /// includes the character set octet.
+    pub(crate) fn parse(bytes: &'a [u8], len: u32) -> Result<(&'a [u8], Self), Error> {
+        let len = len as usize;
+        if len == 0 {
+            return Err(Error::InvalidValue("character string has no character set"));
+        }
+        if bytes.len() < len {
+            return Err(Error::Length(
+                "character string len greater than remaining bytes",
+            ));
+        }
+        Ok((
+            &bytes[len..],
+            Self {
+                charset: bytes[0].into(),
+                bytes: &bytes[1..len],
+            },
+        ))
+    }
+
+    pub fn charset(&self) -> CharacterSet {
+        self.charset
+    }
+
+    /// The encoded characters, excluding the character set octet.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the string if it is UTF-8, or ISO 8859-1 containing only ASCII characters.
+    /// Other character sets cannot be decoded without allocating and return `None`.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.charset {
+            CharacterSet::Utf8 => core::str::from_utf8(self.bytes).ok(),
+            CharacterSet::Iso8859_1 if self.bytes.is_ascii() => {
+                core::str::from_utf8(self.bytes).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse the contents of a context tagged boolean, which is a single octet.
+pub(crate) fn parse_boolean(bytes: &[u8], len: u32) -> Result<(&[u8], bool), Error> {
+    if len != 1 {
+        return Err(Error::InvalidValue("boolean len is not 1"));
+    }
+    if bytes.is_empty() {
+        return Err(Error::Length("boolean len greater than remaining bytes"));
+    }
+    Ok((&bytes[1..], bytes[0] != 0))
+}
+
+/// Parse the contents of an octet string. `len` is the length taken from the tag.
+pub(crate) fn parse_octet_string(bytes: &[u8], len: u32) -> Result<(&[u8], &[u8]), Error> {
+    let len = len as usize;
+    if bytes.len() < len {
+        return Err(Error::Length(
+            "octet string len greater than remaining bytes",
+        ));
+    }
+    Ok((&bytes[len..], &bytes[..len]))
+}
+
+/// A BACnet date. Any field may be unspecified, in which case its accessor returns `None`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date {
+    year: u8,
+    month: u8,
+    day: u8,
+    weekday: u8,
+}
+
+impl Date {
+    /// Parse the contents of a date. `len` is the length taken from the tag.
+    pub(crate) fn parse(bytes: &[u8], len: u32) -> Result<(&[u8], Self), Error> {
+        if len != 4 {
+            return Err(Error::InvalidValue("date len is not 4"));
+        }
+        if bytes.len() < 4 {
+            return Err(Error::Length("date len greater than remaining bytes"));
+        }
+        Ok((
+            &bytes[4..],
+            Self {
+                year: bytes[0],
+                month: bytes[1],
+                day: bytes[2],
+                weekday: bytes[3],
+            },
+        ))
+    }
+
+    pub fn year(&self) -> Option<u16> {
+        unspecified(self.year).map(|y| 1900 + u16::from(y))
+    }
+
+    /// 1-12 for January to December, 13 for odd months and 14 for even months.
+    pub fn month(&self) -> Option<u8> {
+        unspecified(self.month)
+    }
+
+    /// 1-31, 32 for the last day of the month, 33 for odd days and 34 for even days.
+    pub fn day(&self) -> Option<u8> {
+        unspecified(self.day)
+    }
+
+    /// 1-7 for Monday to Sunday.
+    pub fn weekday(&self) -> Option<u8> {
+        unspecified(self.weekday)
+    }
+}
+
+/// A BACnet time. Any field may be unspecified, in which case its accessor returns `None`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    hundredths: u8,
+}
+
+impl Time {
+    /// Parse the contents of a time. `len` is the length taken from the tag.
+    pub(crate) fn parse(bytes: &[u8], len: u32) -> Result<(&[u8], Self), Error> {
+        if len != 4 {
+            return Err(Error::InvalidValue("time len is not 4"));
+        }
+        if bytes.len() < 4 {
+            return Err(Error::Length("time len greater than remaining bytes"));
+        }
+        Ok((
+            &bytes[4..],
+            Self {
+                hour: bytes[0],
+                minute: bytes[1],
+                second: bytes[2],
+                hundredths: bytes[3],
+            },
+        ))
+    }
+
+    pub fn hour(&self) -> Option<u8> {
+        unspecified(self.hour)
+    }
+
+    pub fn minute(&self) -> Option<u8> {
+        unspecified(self.minute)
+    }
+
+    pub fn second(&self) -> Option<u8> {
+        unspecified(self.second)
+    }
+
+    pub fn hundredths(&self) -> Option<u8> {
+        unspecified(self.hundredths)
+    }
+}
+
+fn unspecified(b: u8) -> Option<u8> {
+    match b {
+        0xFF => None,
+        b => Some(b),
+    }
+}
+
+/// A BACnetDateTime, encoded as an application tagged date followed by an application tagged
+/// time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, date) = parse_application_tagged(bytes, TagType::Date, Date::parse)?;
+        let (bytes, time) = parse_application_tagged(bytes, TagType::Time, Time::parse)?;
+        Ok((bytes, Self { date, time }))
+    }
+}
+
+/// A BACnetTimeStamp.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeStamp {
+    Time(Time),
+    SequenceNumber(u32),
+    DateTime(DateTime),
+}
+
+impl TimeStamp {
+    /// Parse a timestamp choice. The bytes should not include any enclosing context tag.
+    pub(crate) fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        if Tag::is_opening(bytes, 2) {
+            let (rest, bytes) = Tag::parse_constructed(bytes, 2)?;
+            let (_, datetime) = DateTime::parse(bytes)?;
+            Ok((rest, Self::DateTime(datetime)))
+        } else if Tag::is_context(bytes, 1) {
+            let (rest, seq) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+            Ok((rest, Self::SequenceNumber(seq)))
+        } else {
+            let (rest, time) = parse_context_tagged(bytes, 0, Time::parse)?;
+            Ok((rest, Self::Time(time)))
+        }
+    }
+}
+
+/// A BACnetObjectPropertyReference.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectPropertyReference {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+}
+
+impl ObjectPropertyReference {
+    /// Parse an object property reference. The bytes should not include any enclosing context
+    /// tag.
+    pub(crate) fn parse(bytes: &[u8]) -> Result<(&[u8], Self), Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, property_id) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 2, parse_unsigned)?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                property_id: property_id.into(),
+                array_index,
+            },
+        ))
+    }
+}
+
+/// A BACnetPropertyValue, as found in WritePropertyMultiple requests and COV notifications.
+pub struct PropertyValue<'a> {
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded value, excluding the enclosing tags.
+    pub value: &'a [u8],
+    pub priority: Option<u8>,
+}
+
+impl<'a> PropertyValue<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, property_id) = parse_context_tagged(bytes, 0, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 1, parse_unsigned)?;
+        let (bytes, value) = Tag::parse_constructed(bytes, 2)?;
+        let (bytes, priority) = parse_optional_context_tagged(bytes, 3, parse_priority)?;
+        Ok((
+            bytes,
+            Self {
+                property_id: property_id.into(),
+                array_index,
+                value,
+                priority,
+            },
+        ))
+    }
+
+    /// Iterate over the value as application tagged values.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.value.into()
+    }
+
+    /// Returns true if the value is a single NULL, which relinquishes a commanded priority.
+    pub fn is_relinquish(&self) -> bool {
+        is_relinquish(self.value)
+    }
+}
+
+/// Iterates over a list of BACnetPropertyValue.
+pub struct PropertyValues<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for PropertyValues<'a> {
+    type Item = Result<PropertyValue<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, PropertyValue::parse)
+    }
+}
+
+impl<'a> From<&'a [u8]> for PropertyValues<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+/// Parse a command priority, which must be in the range 1-16.
+pub(crate) fn parse_priority(bytes: &[u8], len: u32) -> Result<(&[u8], u8), Error> {
+    let (bytes, priority) = parse_unsigned8(bytes, len)?;
+    if !(1..=16).contains(&priority) {
+        return Err(Error::InvalidValue("priority not in range 1-16"));
+    }
+    Ok((bytes, priority))
+}
+
+/// Returns true if the encoded value is a single application tagged NULL.
+pub(crate) fn is_relinquish(value: &[u8]) -> bool {
+    value == [0x00]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::object_type::ObjectType;
+
+    fn parse_one(bytes: &[u8]) -> ApplicationValue<'_> {
+        let (rest, value) = ApplicationValue::parse(bytes).unwrap();
+        assert!(rest.is_empty());
+        value
+    }
+
+    #[test]
+    fn application_value_primitives_test() {
+        assert_eq!(parse_one(&[0x00]), ApplicationValue::Null);
+        assert_eq!(parse_one(&[0x10]), ApplicationValue::Boolean(false));
+        assert_eq!(parse_one(&[0x11]), ApplicationValue::Boolean(true));
+        assert_eq!(parse_one(&[0x21, 0xff]), ApplicationValue::Unsigned(255));
+        assert_eq!(
+            parse_one(&[0x25, 0x05, 0x01, 0x00, 0x00, 0x00, 0x00]),
+            ApplicationValue::Unsigned(0x01_0000_0000)
+        );
+        assert_eq!(
+            parse_one(&[0x25, 0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            ApplicationValue::Unsigned(u64::MAX)
+        );
+        assert_eq!(parse_one(&[0x31, 0xff]), ApplicationValue::Signed(-1));
+        assert_eq!(parse_one(&[0x31, 0x7f]), ApplicationValue::Signed(127));
+        assert_eq!(
+            parse_one(&[0x32, 0xff, 0x38]),
+            ApplicationValue::Signed(-200)
+        );
+        assert_eq!(
+            parse_one(&[0x25, 0x06, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            ApplicationValue::Unsigned(0x8000_0000_0000)
+        );
+        assert_eq!(
+            parse_one(&[0x35, 0x06, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            ApplicationValue::Signed(-0x8000_0000_0000)
+        );
+        assert_eq!(
+            parse_one(&[0x44, 0xc0, 0x20, 0x00, 0x00]),
+            ApplicationValue::Real(-2.5)
+        );
+        assert_eq!(
+            parse_one(&[0x55, 0x08, 0x40, 0x09, 0x21, 0xfb, 0x54, 0x44, 0x2d, 0x18]),
+            ApplicationValue::Double(core::f64::consts::PI)
+        );
+        assert_eq!(
+            parse_one(&[0x62, 0x12, 0x34]),
+            ApplicationValue::OctetString(&[0x12, 0x34])
+        );
+        assert_eq!(parse_one(&[0x91, 0x03]), ApplicationValue::Enumerated(3));
+    }
+
+    #[test]
+    fn application_value_strings_test() {
+        match parse_one(&[0x74, 0x00, 0x41, 0x42, 0x43]) {
+            ApplicationValue::CharacterString(s) => {
+                assert_eq!(s.charset(), CharacterSet::Utf8);
+                assert_eq!(s.as_str(), Some("ABC"));
+            }
+            _ => panic!("should be a character string"),
+        }
+        match parse_one(&[0x83, 0x03, 0xa0, 0x80]) {
+            ApplicationValue::BitString(b) => {
+                assert_eq!(b.len(), 13);
+                assert_eq!(b.bit(0), Some(true));
+                assert_eq!(b.bit(1), Some(false));
+                assert_eq!(b.bit(2), Some(true));
+                assert_eq!(b.bit(8), Some(true));
+                assert_eq!(b.bit(12), Some(false));
+                assert_eq!(b.bit(13), None);
+            }
+            _ => panic!("should be a bit string"),
+        }
+        match parse_one(&[0x81, 0x00]) {
+            ApplicationValue::BitString(b) => assert!(b.is_empty()),
+            _ => panic!("should be a bit string"),
+        }
+        assert!(ApplicationValue::parse(&[0x82, 0x08, 0x00]).is_err());
+    }
+
+    #[test]
+    fn application_value_date_time_test() {
+        match parse_one(&[0xa4, 0x7a, 0x0b, 0x11, 0xff]) {
+            ApplicationValue::Date(d) => {
+                assert_eq!(d.year(), Some(2022));
+                assert_eq!(d.month(), Some(11));
+                assert_eq!(d.day(), Some(17));
+                assert_eq!(d.weekday(), None);
+            }
+            _ => panic!("should be a date"),
+        }
+        match parse_one(&[0xb4, 0x0d, 0x1e, 0x00, 0x00]) {
+            ApplicationValue::Time(t) => {
+                assert_eq!(t.hour(), Some(13));
+                assert_eq!(t.minute(), Some(30));
+            }
+            _ => panic!("should be a time"),
+        }
+        match parse_one(&[0xc4, 0x02, 0x00, 0x00, 0x6f]) {
+            ApplicationValue::ObjectId(id) => {
+                assert_eq!(id.object_type, ObjectType::ObjectDevice);
+                assert_eq!(id.instance, 111);
+            }
+            _ => panic!("should be an object id"),
+        }
+    }
+
+    #[test]
+    fn application_value_errors_test() {
+        assert!(ApplicationValue::parse(&[]).is_err());
+        // context tag
+        assert!(ApplicationValue::parse(&[0x09, 0x01]).is_err());
+        // opening tag
+        assert!(ApplicationValue::parse(&[0x0e]).is_err());
+        // reserved application tag
+        assert!(ApplicationValue::parse(&[0xd1, 0x00]).is_err());
+        // not enough bytes
+        assert!(ApplicationValue::parse(&[0x22, 0x01]).is_err());
+        assert!(ApplicationValue::parse(&[0x44, 0x00, 0x00]).is_err());
+        // wrong len
+        assert!(ApplicationValue::parse(&[0x43, 0x00, 0x00, 0x00]).is_err());
+        assert!(ApplicationValue::parse(&[0x25, 0x09, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        // null with contents
+        assert!(ApplicationValue::parse(&[0x03, 0x00, 0x00, 0x00]).is_err());
+        assert!(ApplicationValue::parse(&[0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn unsigned_len_test() {
+        assert!(ApplicationValue::parse(&[0x90]).is_err());
+        assert_eq!(parse_one(&[0x91, 0x01]), ApplicationValue::Enumerated(0x01));
+        assert_eq!(
+            parse_one(&[0x92, 0x01, 0x02]),
+            ApplicationValue::Enumerated(0x0102)
+        );
+        assert_eq!(
+            parse_one(&[0x93, 0x01, 0x02, 0x03]),
+            ApplicationValue::Enumerated(0x010203)
+        );
+        assert_eq!(
+            parse_one(&[0x94, 0x01, 0x02, 0x03, 0x04]),
+            ApplicationValue::Enumerated(0x01020304)
+        );
+        assert!(ApplicationValue::parse(&[0x95, 0x05, 0x01, 0x02, 0x03, 0x04, 0x05]).is_err());
+        assert!(ApplicationValue::parse(&[0x93, 0x01, 0x02]).is_err());
+    }
+
+    #[test]
+    fn application_values_test() {
+        let bytes: &[u8] = &[0x00, 0x11, 0x21, 0x01, 0x09, 0x00, 0x21, 0x02];
+        let mut values = ApplicationValues::from(bytes);
+        assert_eq!(values.next().unwrap().unwrap(), ApplicationValue::Null);
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Boolean(true)
+        );
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Unsigned(1)
+        );
+        assert!(values.next().unwrap().is_err());
+        assert!(values.next().is_none());
+    }
+}
@@ -0,0 +1,197 @@
+use super::{
+    tag::{parse_context_tagged, parse_next, parse_optional_context_tagged, Tag},
+    value::{is_relinquish, parse_priority, ApplicationValues, PropertyValues},
+};
+use crate::nsdu::{object_type::ObjectId, parse_unsigned, property_id::PropertyId};
+use crate::Error;
+
+/// The parameters of a WriteProperty service request.
+pub struct WritePropertyRequest<'a> {
+    pub object_id: ObjectId,
+    pub property_id: PropertyId,
+    pub array_index: Option<u32>,
+    /// The encoded property value, excluding the enclosing tags.
+    pub property_value: &'a [u8],
+    pub priority: Option<u8>,
+}
+
+impl<'a> WritePropertyRequest<'a> {
+    /// Attempt to parse a WritePropertyRequest from the service request bytes of a confirmed
+    /// request, i.e. `ConfirmedRequestHeader::service_request()`.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, property_id) = parse_context_tagged(bytes, 1, parse_unsigned)?;
+        let (bytes, array_index) = parse_optional_context_tagged(bytes, 2, parse_unsigned)?;
+        let (bytes, property_value) = Tag::parse_constructed(bytes, 3)?;
+        let (_, priority) = parse_optional_context_tagged(bytes, 4, parse_priority)?;
+        Ok(Self {
+            object_id,
+            property_id: property_id.into(),
+            array_index,
+            property_value,
+            priority,
+        })
+    }
+
+    /// Iterate over the property value as application tagged values.
+    pub fn values(&self) -> ApplicationValues<'a> {
+        self.property_value.into()
+    }
+
+    /// Returns true if the value is a single NULL, which relinquishes a commanded priority.
+    pub fn is_relinquish(&self) -> bool {
+        is_relinquish(self.property_value)
+    }
+}
+
+/// Iterates over the write-access-specifications of a WritePropertyMultiple service request.
+///
+/// Construct it from the service request bytes of a confirmed request, i.e.
+/// `ConfirmedRequestHeader::service_request()`.
+pub struct WriteAccessSpecifications<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for WriteAccessSpecifications<'a> {
+    type Item = Result<WriteAccessSpecification<'a>, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        parse_next(&mut self.bytes, WriteAccessSpecification::parse)
+    }
+}
+
+impl<'a> From<&'a [u8]> for WriteAccessSpecifications<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+/// An object and the property values to write to it.
+pub struct WriteAccessSpecification<'a> {
+    pub object_id: ObjectId,
+    /// The encoded list of BACnetPropertyValue, excluding the enclosing tags.
+    pub list_of_properties: &'a [u8],
+}
+
+impl<'a> WriteAccessSpecification<'a> {
+    fn parse(bytes: &'a [u8]) -> Result<(&'a [u8], Self), Error> {
+        let (bytes, object_id) = parse_context_tagged(bytes, 0, ObjectId::parse)?;
+        let (bytes, list_of_properties) = Tag::parse_constructed(bytes, 1)?;
+        Ok((
+            bytes,
+            Self {
+                object_id,
+                list_of_properties,
+            },
+        ))
+    }
+
+    pub fn properties(&self) -> PropertyValues<'a> {
+        self.list_of_properties.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nsdu::apdu::{
+        confirmed_request_pdu::ConfirmedRequestHeader, value::ApplicationValue,
+        ConfirmedServiceChoice,
+    };
+    use crate::nsdu::{object_type::ObjectType, parse_apdu};
+
+    #[test]
+    fn write_property_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x03, 0x0f, // header
+            0x0c, 0x00, 0x80, 0x00, 0x02, 0x19, 0x55, 0x3e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x3f,
+            0x49, 0x08,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::WriteProperty
+        );
+        let wp = WritePropertyRequest::parse(header.service_request()).unwrap();
+        assert_eq!(wp.object_id.object_type, ObjectType::ObjectAnalogValue);
+        assert_eq!(wp.object_id.instance, 2);
+        assert_eq!(wp.property_id, PropertyId::PropPresentValue);
+        assert_eq!(wp.array_index, None);
+        assert_eq!(wp.priority, Some(8));
+        assert!(!wp.is_relinquish());
+        let mut values = wp.values();
+        assert_eq!(
+            values.next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        assert!(values.next().is_none());
+
+        // relinquish without a priority
+        let bytes: &[u8] = &[0x0c, 0x00, 0x80, 0x00, 0x02, 0x19, 0x55, 0x3e, 0x00, 0x3f];
+        let wp = WritePropertyRequest::parse(bytes).unwrap();
+        assert_eq!(wp.priority, None);
+        assert!(wp.is_relinquish());
+
+        // priority out of range
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x80, 0x00, 0x02, 0x19, 0x55, 0x3e, 0x00, 0x3f, 0x49, 0x11,
+        ];
+        assert!(WritePropertyRequest::parse(bytes).is_err());
+        let bytes: &[u8] = &[
+            0x0c, 0x00, 0x80, 0x00, 0x02, 0x19, 0x55, 0x3e, 0x00, 0x3f, 0x49, 0x00,
+        ];
+        assert!(WritePropertyRequest::parse(bytes).is_err());
+    }
+
+    #[test]
+    fn write_property_multiple_test() {
+        let bytes: &[u8] = &[
+            0x00, 0x05, 0x04, 0x10, // header
+            0x0c, 0x00, 0x80, 0x00, 0x02, 0x1e, // AV 2
+            0x09, 0x55, 0x2e, 0x44, 0x42, 0x90, 0x00, 0x00, 0x2f, 0x39, 0x0a, // present value
+            0x09, 0x1c, 0x2e, 0x75, 0x03, 0x00, 0x68, 0x69, 0x2f, // description
+            0x1f, //
+            0x0c, 0x01, 0x00, 0x00, 0x03, 0x1e, // BO 3
+            0x09, 0x55, 0x2e, 0x00, 0x2f, 0x39, 0x10, // relinquish present value
+            0x1f,
+        ];
+        let apdu = parse_apdu(bytes).unwrap();
+        let header = ConfirmedRequestHeader::parse(&apdu).unwrap();
+        assert_eq!(
+            header.service_choice(),
+            ConfirmedServiceChoice::WritePropertyMultiple
+        );
+        let mut specs = WriteAccessSpecifications::from(header.service_request());
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectAnalogValue);
+        let mut props = spec.properties();
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropPresentValue);
+        assert_eq!(prop.array_index, None);
+        assert_eq!(prop.priority, Some(10));
+        assert!(!prop.is_relinquish());
+        assert_eq!(
+            prop.values().next().unwrap().unwrap(),
+            ApplicationValue::Real(72.0)
+        );
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.property_id, PropertyId::PropDescription);
+        assert_eq!(prop.priority, None);
+        match prop.values().next().unwrap().unwrap() {
+            ApplicationValue::CharacterString(s) => assert_eq!(s.as_str(), Some("hi")),
+            _ => panic!("should be a character string"),
+        }
+        assert!(props.next().is_none());
+
+        let spec = specs.next().unwrap().unwrap();
+        assert_eq!(spec.object_id.object_type, ObjectType::ObjectBinaryOutput);
+        assert_eq!(spec.object_id.instance, 3);
+        let mut props = spec.properties();
+        let prop = props.next().unwrap().unwrap();
+        assert_eq!(prop.priority, Some(16));
+        assert!(prop.is_relinquish());
+        assert!(props.next().is_none());
+        assert!(specs.next().is_none());
+    }
+}
@@ -35,8 +35,11 @@
 ///   For more information: info@bac-test.com
 ///   For access to source code:  info@bac-test.com
 ///          or      www.github.com/bacnettesting/bacnet-stack
+use super::parse_unsigned;
+use crate::Error;
 use arrayref::array_ref;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ObjectType {
     ObjectAnalogInput,
     ObjectAnalogOutput,
@@ -107,9 +110,18 @@ pub enum ObjectType {
 }
 
 impl ObjectType {
-    pub fn parse(b: &[u8]) -> Self {
+    pub fn parse(b: &[u8]) -> Result<Self, Error> {
         // FIXME: parse properly
-        match u16::from_be_bytes(*array_ref!(b, 0, 2)) {
+        if b.len() < 2 {
+            return Err(Error::Length("insufficient size for object type"));
+        }
+        Ok(u16::from_be_bytes(*array_ref!(b, 0, 2)).into())
+    }
+}
+
+impl From<u16> for ObjectType {
+    fn from(b: u16) -> Self {
+        match b {
             0 => Self::ObjectAnalogInput,
             1 => Self::ObjectAnalogOutput,
             2 => Self::ObjectAnalogValue,
@@ -173,3 +185,31 @@ impl ObjectType {
         }
     }
 }
+
+/// A BACnet object identifier, which is encoded as a 10 bit object type followed by a 22 bit
+/// instance number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectId {
+    pub object_type: ObjectType,
+    pub instance: u32,
+}
+
+impl ObjectId {
+    /// Parse the contents of an object identifier. `len` is the length taken from the tag.
+    pub(crate) fn parse(bytes: &[u8], len: u32) -> Result<(&[u8], Self), Error> {
+        if len != 4 {
+            return Err(Error::InvalidValue("object id len is not 4"));
+        }
+        let (bytes, id) = parse_unsigned(bytes, len)?;
+        Ok((bytes, id.into()))
+    }
+}
+
+impl From<u32> for ObjectId {
+    fn from(b: u32) -> Self {
+        Self {
+            object_type: ((b >> 22) as u16).into(),
+            instance: b & 0x003F_FFFF,
+        }
+    }
+}
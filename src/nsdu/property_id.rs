@@ -35,8 +35,10 @@
 ///   For more information: info@bac-test.com
 ///   For access to source code:  info@bac-test.com
 ///          or      www.github.com/bacnettesting/bacnet-stack
+use crate::Error;
 use arrayref::array_ref;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PropertyId {
     PropAckedTransitions,
     PropAckRequired,
@@ -509,9 +511,18 @@ pub enum PropertyId {
 }
 
 impl PropertyId {
-    pub fn parse(b: &[u8]) -> Self {
+    pub fn parse(b: &[u8]) -> Result<Self, Error> {
         // FIXME: parse properly
-        match u16::from_be_bytes(*array_ref!(b, 0, 2)) {
+        if b.len() < 2 {
+            return Err(Error::Length("insufficient size for property id"));
+        }
+        Ok(u32::from(u16::from_be_bytes(*array_ref!(b, 0, 2))).into())
+    }
+}
+
+impl From<u32> for PropertyId {
+    fn from(b: u32) -> Self {
+        match b {
             0 => Self::PropAckedTransitions,
             1 => Self::PropAckRequired,
             2 => Self::PropAction,
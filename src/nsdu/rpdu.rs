@@ -1,15 +1,12 @@
 use crate::Error;
 use arrayref::array_ref;
 
-pub fn parse_rpdu(bytes: &[u8]) -> Result<RPDU, Error> {
+pub fn parse_rpdu(bytes: &[u8]) -> Result<RPDU<'_>, Error> {
     if bytes.is_empty() {
         return Err(Error::Length("no rpdu data"));
     }
     Ok(match bytes[0] {
-        0x00 => RPDU::WhoIsRouterToNetwork(match try_parse_dnet(bytes) {
-            Ok(dnet) => Some(dnet),
-            Err(_) => None,
-        }),
+        0x00 => RPDU::WhoIsRouterToNetwork(try_parse_dnet(bytes).ok()),
         0x01 => RPDU::IAmRouterToNetwork(bytes.into()),
         0x02 => RPDU::ICouldBeRouterToNetwork(try_parse_dnet(bytes)?), // TODO: need to verify this one
         0x03 => RPDU::RejectMessageToNetwork,
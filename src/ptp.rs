@@ -0,0 +1,247 @@
+//! BACnet Point-To-Point (PTP) frames, as described in Clause 10.
+use super::mstp::{compute_data_crc, compute_header_crc, CRCs};
+use super::npdu::*;
+use super::*;
+use arrayref::array_ref;
+use core::convert::From;
+
+/// Parses a PTP frame that has already had its DLE escaping removed, see `decode_dle`. The
+/// header and data CRCs are re-computed and can be compared with `frame.crcs()`.
+pub fn parse_ptp(bytes: &[u8]) -> Result<PTPFrame<'_>, Error> {
+    if bytes.len() < 6 {
+        return Err(Error::Length("data is shorter than minimum ptp frame size"));
+    }
+    if bytes[0] != 0x55 || bytes[1] != 0xFF {
+        return Err(Error::InvalidValue("not the ptp preamble"));
+    }
+    let len = u16::from_be_bytes(*array_ref!(bytes, 3, 2));
+    // 8 comes from (header = 6) + (crc = 2)
+    let framelen = if len == 0 { 6 } else { 8 + len as usize };
+    if framelen != bytes.len() {
+        return Err(Error::Length("ptp length does not match data size"));
+    }
+
+    let mut crcs = CRCs {
+        header_actual: bytes[5],
+        header_computed: compute_header_crc(&bytes[2..5]),
+        ..Default::default()
+    };
+    let data = if len > 0 {
+        let data = &bytes[6..framelen - 2];
+        crcs.data_actual = u16::from_le_bytes(*array_ref!(bytes, framelen - 2, 2));
+        crcs.data_computed = compute_data_crc(data);
+        data
+    } else {
+        &[]
+    };
+
+    let mut frame = PTPFrame {
+        frame_type: bytes[2],
+        crcs,
+        data,
+        npdu: None,
+    };
+    if frame.frame_type().is_data() {
+        if let Ok(npdu) = parse_npdu(data) {
+            frame.npdu = Some(npdu);
+        }
+    }
+    Ok(frame)
+}
+
+/// Removes the DLE escaping from `bytes` into `buf` and then parses the frame with `parse_ptp`.
+pub fn parse_ptp_escaped<'a>(bytes: &[u8], buf: &'a mut [u8]) -> Result<PTPFrame<'a>, Error> {
+    let len = decode_dle(bytes, buf)?;
+    parse_ptp(&buf[..len])
+}
+
+/// Removes the DLE escaping from `bytes` into `buf`. A DLE (0x10) followed by a byte is decoded
+/// as that byte with its high bit cleared, e.g. `0x10 0x91` becomes XON (0x11). Returns the
+/// number of bytes written.
+pub fn decode_dle(bytes: &[u8], buf: &mut [u8]) -> Result<usize, Error> {
+    let mut len = 0;
+    let mut iter = bytes.iter();
+    while let Some(&b) = iter.next() {
+        let b = if b == DLE {
+            match iter.next() {
+                Some(&b) => b & 0x7F,
+                None => return Err(Error::Length("dle at end of data")),
+            }
+        } else {
+            b
+        };
+        match buf.get_mut(len) {
+            Some(out) => *out = b,
+            None => return Err(Error::Length("buffer too small for decoded ptp frame")),
+        }
+        len += 1;
+    }
+    Ok(len)
+}
+
+const DLE: u8 = 0x10;
+
+pub struct PTPFrame<'a> {
+    frame_type: u8,
+    crcs: CRCs,
+    data: &'a [u8],
+    npdu: Option<NPDU<'a>>,
+}
+
+impl<'a> PTPFrame<'a> {
+    pub fn frame_type(&self) -> PTPFrameType {
+        self.frame_type.into()
+    }
+    pub fn frame_type_byte(&self) -> u8 {
+        self.frame_type
+    }
+    pub fn data_len(&self) -> u16 {
+        self.data.len() as u16
+    }
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+    pub fn crcs(&self) -> CRCs {
+        self.crcs
+    }
+    /// The NPDU of a Data 0 or Data 1 frame.
+    pub fn npdu(&self) -> &Option<NPDU<'a>> {
+        &self.npdu
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PTPFrameType {
+    HeartbeatXOFF,
+    HeartbeatXON,
+    Data0,
+    Data1,
+    DataAck0XOFF,
+    DataAck1XOFF,
+    DataAck0XON,
+    DataAck1XON,
+    DataNak0XOFF,
+    DataNak1XOFF,
+    DataNak0XON,
+    DataNak1XON,
+    ConnectRequest,
+    ConnectResponse,
+    DisconnectRequest,
+    DisconnectResponse,
+    TestRequest,
+    TestResponse,
+    Unknown(u8),
+}
+
+impl PTPFrameType {
+    pub fn is_data(self) -> bool {
+        matches!(self, Self::Data0 | Self::Data1)
+    }
+}
+
+impl From<u8> for PTPFrameType {
+    fn from(b: u8) -> Self {
+        match b {
+            0x00 => Self::HeartbeatXOFF,
+            0x01 => Self::HeartbeatXON,
+            0x02 => Self::Data0,
+            0x03 => Self::Data1,
+            0x04 => Self::DataAck0XOFF,
+            0x05 => Self::DataAck1XOFF,
+            0x06 => Self::DataAck0XON,
+            0x07 => Self::DataAck1XON,
+            0x08 => Self::DataNak0XOFF,
+            0x09 => Self::DataNak1XOFF,
+            0x0a => Self::DataNak0XON,
+            0x0b => Self::DataNak1XON,
+            0x0c => Self::ConnectRequest,
+            0x0d => Self::ConnectResponse,
+            0x0e => Self::DisconnectRequest,
+            0x0f => Self::DisconnectResponse,
+            0x14 => Self::TestRequest,
+            0x15 => Self::TestResponse,
+            b => Self::Unknown(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ESCAPED: &[u8] = &[
+        0x55, 0xff, 0x02, 0x00, 0x07, 0x21, // header
+        0x01, 0x04, 0x00, 0x05, 0x01, 0x0c, 0x10, 0x91, // NPDU
+        0x2a, 0x9f, // data crc
+    ];
+
+    #[test]
+    fn parse_ptp_data() {
+        let mut buf = [0u8; 16];
+        let frame = parse_ptp_escaped(ESCAPED, &mut buf).unwrap();
+        assert_eq!(frame.frame_type(), PTPFrameType::Data0);
+        assert_eq!(frame.data_len(), 7);
+        let (actual, computed) = frame.crcs().header();
+        assert_eq!(actual, computed);
+        let (actual, computed) = frame.crcs().data();
+        assert_eq!(actual, computed);
+        assert_eq!(actual, 0x9f2a);
+        let npdu = frame.npdu().as_ref().unwrap();
+        assert!(npdu.is_expecting_reply());
+        assert_eq!(npdu.payload(), &[0x00, 0x05, 0x01, 0x0c, 0x11]);
+    }
+
+    #[test]
+    fn parse_ptp_unescaped_data() {
+        // the escaped frame only fails the length check
+        assert!(parse_ptp(ESCAPED).is_err());
+        let mut buf = [0u8; 15];
+        let len = decode_dle(ESCAPED, &mut buf).unwrap();
+        assert_eq!(len, 15);
+        assert_eq!(buf[12], 0x11);
+        assert!(parse_ptp(&buf).unwrap().npdu().is_some());
+        // DATA 1 frame with a corrupted data crc
+        buf[2] = 0x03;
+        buf[5] = compute_header_crc(&buf[2..5]);
+        buf[13] = 0x2b;
+        let frame = parse_ptp(&buf).unwrap();
+        assert_eq!(frame.frame_type(), PTPFrameType::Data1);
+        let (actual, computed) = frame.crcs().data();
+        assert_ne!(actual, computed);
+    }
+
+    #[test]
+    fn parse_ptp_control_frames() {
+        let frame = parse_ptp(&[0x55, 0xff, 0x01, 0x00, 0x00, 0x76]).unwrap();
+        assert_eq!(frame.frame_type(), PTPFrameType::HeartbeatXON);
+        let (actual, computed) = frame.crcs().header();
+        assert_eq!(actual, computed);
+        assert!(frame.data().is_empty());
+        assert!(frame.npdu().is_none());
+
+        // the disconnect reason 0x13 is escaped
+        let escaped = &[0x55, 0xff, 0x0e, 0x00, 0x01, 0x89, 0x10, 0x93, 0x62, 0xd2];
+        let mut buf = [0u8; 9];
+        let frame = parse_ptp_escaped(escaped, &mut buf).unwrap();
+        assert_eq!(frame.frame_type(), PTPFrameType::DisconnectRequest);
+        assert_eq!(frame.data(), &[0x13]);
+        let (actual, computed) = frame.crcs().data();
+        assert_eq!(actual, computed);
+        assert!(frame.npdu().is_none());
+
+        assert_eq!(PTPFrameType::from(0x14), PTPFrameType::TestRequest);
+        assert_eq!(PTPFrameType::from(0x15), PTPFrameType::TestResponse);
+        assert_eq!(PTPFrameType::from(0x10), PTPFrameType::Unknown(0x10));
+    }
+
+    #[test]
+    fn parse_ptp_invalid() {
+        assert!(parse_ptp(&[0x55, 0xff, 0x00, 0x00, 0x00]).is_err());
+        assert!(parse_ptp(&[0x55, 0xfe, 0x00, 0x00, 0x00, 0xee]).is_err());
+        // data length without data
+        assert!(parse_ptp(&[0x55, 0xff, 0x02, 0x00, 0x01, 0x00]).is_err());
+        let mut buf = [0u8; 16];
+        assert!(decode_dle(&[0x55, 0x10], &mut buf).is_err());
+        assert!(parse_ptp_escaped(ESCAPED, &mut buf[..14]).is_err());
+    }
+}